@@ -0,0 +1,58 @@
+use bevy::prelude::*;
+use crate::consts::SCALE;
+use crate::game::Game;
+use crate::game::bonus::BONUS_POSITION;
+use crate::game::map::TileInformation;
+use crate::map::tile_translation;
+use crate::sprite::load_sprite_sheet;
+
+/// Marks the fruit sprite below the ghost house.
+pub(crate) struct BonusSprite;
+
+pub(crate) struct BonusPlugin;
+
+impl Plugin for BonusPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .add_startup_system(setup_bonus.system())
+            .add_system(show_bonus.system())
+        ;
+    }
+}
+
+fn setup_bonus(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let sheet = load_sprite_sheet(&asset_server, &mut textures, &mut texture_atlases);
+    let mut translation = tile_translation(TileInformation::from(BONUS_POSITION));
+    translation.set_z(1.0);
+    let mut sprite_sheet_components = SpriteSheetComponents {
+        texture_atlas: sheet,
+        transform: Transform::from_scale(SCALE)
+            .with_translation(translation),
+        ..Default::default()
+    };
+    sprite_sheet_components.draw.is_visible = false;
+    commands
+        .spawn(sprite_sheet_components)
+        .with(BonusSprite)
+    ;
+}
+
+fn show_bonus(
+    game: Res<Game>,
+    mut query: Query<(&BonusSprite, &mut TextureAtlasSprite, &mut Draw)>,
+) {
+    for (_, mut atlas_sprite, mut draw) in &mut query.iter() {
+        match game.bonus() {
+            Some(bonus) => {
+                atlas_sprite.index = bonus.fruit().texture_index();
+                draw.is_visible = true;
+            },
+            None => draw.is_visible = false,
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+
+use crate::consts::{SCALE, TILE_SIZE, TILES_WIDE};
+use crate::game::Game;
+use crate::game::cutscene::Role;
+use crate::map::{map_tile_to_translation, tile_translation};
+use crate::sprite::load_sprite_sheet;
+use crate::tick::FixedTick;
+
+// Intermissions play over a black screen that leaves the scores and the bottom row showing.
+const BACKDROP_Z: f32 = 4.0;
+const CUTSCENE_Z: f32 = 5.0;
+const BACKDROP_BOTTOM_TILE: u32 = 2;
+const BACKDROP_TILES_HIGH: u32 = 32;
+
+// The most sprite sheet cells any one actor is drawn with.
+const MAX_CELLS: usize = 4;
+
+/// Draws the intermissions the game plays between some levels.
+pub(crate) struct CutscenePlugin;
+
+struct CutsceneBackdrop;
+
+/// One of the cells an actor in the cutscene is drawn with. Every role gets a full set up
+/// front; the ones not needed are hidden.
+struct CutscenePart {
+    role: Role,
+    cell: usize,
+}
+
+impl Plugin for CutscenePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .add_startup_system(setup_cutscene.system())
+            .add_system(show_backdrop.system())
+            .add_system(draw_actors.system())
+        ;
+    }
+}
+
+fn setup_cutscene(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let sheet = load_sprite_sheet(&asset_server, &mut textures, &mut texture_atlases);
+
+    let mut translation = map_tile_to_translation(
+        TILES_WIDE as u32 / 2, BACKDROP_BOTTOM_TILE + BACKDROP_TILES_HIGH / 2, Some(0), Some(0));
+    translation.set_z(BACKDROP_Z);
+    commands
+        .spawn(SpriteComponents {
+            material: materials.add(Color::rgb(0.0, 0.0, 0.0).into()),
+            transform: Transform::from_translation(translation),
+            sprite: Sprite::new(Vec2::new(TILES_WIDE as f32 * TILE_SIZE * SCALE,
+                                          BACKDROP_TILES_HIGH as f32 * TILE_SIZE * SCALE)),
+            ..Default::default()
+        })
+        .with(CutsceneBackdrop)
+    ;
+
+    for &role in Role::ALL.iter() {
+        for cell in 0..MAX_CELLS {
+            commands
+                .spawn(SpriteSheetComponents {
+                    texture_atlas: sheet,
+                    transform: Transform::from_scale(SCALE),
+                    ..Default::default()
+                })
+                .with(CutscenePart { role, cell })
+            ;
+        }
+    }
+}
+
+fn show_backdrop(
+    game: Res<Game>,
+    mut query: Query<(&CutsceneBackdrop, &mut Draw)>,
+) {
+    for (_, mut draw) in &mut query.iter() {
+        draw.is_visible = game.intermission().is_some();
+    }
+}
+
+// Like the sprites in the maze, actors are placed between their last two tick positions.
+fn draw_actors(
+    game: Res<Game>,
+    fixed_tick: Res<FixedTick>,
+    mut query: Query<(&CutscenePart, &mut TextureAtlasSprite, &mut Transform, &mut Draw)>,
+) {
+    let alpha = fixed_tick.alpha();
+    for (part, mut atlas_sprite, mut transform, mut draw) in &mut query.iter() {
+        let actor = game.intermission().and_then(|cutscene| cutscene.actor(part.role));
+        let (actor, (index, dx, dy)) = match actor
+            .and_then(|actor| actor.cells().get(part.cell).map(|&cell| (actor, cell))) {
+            Some(found) => found,
+            None => {
+                draw.is_visible = false;
+                continue;
+            },
+        };
+        let sprite = actor.sprite();
+        let previous = tile_translation(sprite.previous_tile_info());
+        let current = tile_translation(sprite.tile_info());
+        let translation = transform.translation_mut();
+        translation.set_x(previous.x() + (current.x() - previous.x()) * alpha
+                          + dx as f32 * SCALE);
+        translation.set_y(previous.y() + (current.y() - previous.y()) * alpha
+                          + dy as f32 * SCALE);
+        translation.set_z(CUTSCENE_Z);
+        atlas_sprite.index = index;
+        draw.is_visible = true;
+    }
+}
@@ -1,14 +1,28 @@
 use bevy::prelude::*;
 
-use crate::map::{Map, map_tile_to_translation};
+use crate::game::{Game, TICKS_PER_SECOND};
+use crate::game::mode::GhostMode;
+use crate::map::map_tile_to_translation;
 use crate::consts::{BOUNDING_BOX, SCALE, TILES_WIDE, TILES_HIGH};
+use crate::text::{TextColor, glyph_index, load_font};
+
+// The ghosts' mode is read out top right, clear of the scores: S or C, the phase of the
+// schedule, and the seconds left in it.
+const MODE_TILE: (u32, u32) = (21, 34);
+const MODE_CHARS: usize = 7;
+const MODE_Z: f32 = 3.0;
 
 pub(crate) struct DebugPlugin;
 
+/// One character of the mode readout.
+struct ModeChar(usize);
+
 impl Plugin for DebugPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app
             .add_startup_system(setup_grid.system())
+            .add_startup_system(setup_mode_readout.system())
+            .add_system(show_mode.system())
         ;
     }
 }
@@ -16,7 +30,7 @@ impl Plugin for DebugPlugin {
 fn setup_grid(
     mut commands: Commands,
     mut materials: ResMut<Assets<ColorMaterial>>,
-    map: Res<Map>
+    game: Res<Game>
 ){
     let grid_material = materials.add(Color::rgba(0.4, 0.0, 0.0, 0.4).into());
     // Vertical lines
@@ -50,7 +64,8 @@ fn setup_grid(
     let valid_path_material = materials.add(Color::rgba(0.6, 0.6, 0.6, 0.3).into());
     for x in 0..TILES_WIDE {
         for y in 0..TILES_HIGH {
-            let (tile, mut translation) = map.get_tile(x as u32, y as u32);
+            let tile = game.map().get_tile(x as u32, y as u32);
+            let mut translation = map_tile_to_translation(x as u32, y as u32, None, None);
             translation.set_z(2.0);
             if tile.is_valid_path() {
                 commands
@@ -67,3 +82,47 @@ fn setup_grid(
     }
 }
 
+fn setup_mode_readout(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let font = load_font(&asset_server, &mut textures, &mut texture_atlases);
+    let (x, y) = MODE_TILE;
+    for i in 0..MODE_CHARS {
+        let mut translation = map_tile_to_translation(x + i as u32, y, None, None);
+        translation.set_z(MODE_Z);
+        commands
+            .spawn(SpriteSheetComponents {
+                texture_atlas: font,
+                transform: Transform::from_scale(SCALE)
+                    .with_translation(translation),
+                ..Default::default()
+            })
+            .with(ModeChar(i))
+        ;
+    }
+}
+
+fn show_mode(
+    game: Res<Game>,
+    mut query: Query<(&ModeChar, &mut TextureAtlasSprite)>,
+) {
+    let timer = game.mode_timer();
+    let mode = match timer.mode() {
+        GhostMode::Scatter => 'S',
+        GhostMode::Chase => 'C',
+    };
+    let readout = match timer.ticks_left() {
+        Some(ticks) => {
+            let seconds = (ticks + TICKS_PER_SECOND - 1) / TICKS_PER_SECOND;
+            format!("{}{} {}", mode, timer.phase(), seconds)
+        },
+        None => format!("{}{}", mode, timer.phase()),
+    };
+    for (ch, mut atlas_sprite) in &mut query.iter() {
+        let ch = readout.chars().nth(ch.0).unwrap_or(' ');
+        atlas_sprite.index = glyph_index(ch, TextColor::Red);
+    }
+}
@@ -0,0 +1,163 @@
+use std::collections::VecDeque;
+
+use super::Game;
+use super::consts::{Direction, TILES_WIDE, TILES_HIGH};
+use super::ghost::GhostState;
+use super::map::TileActor;
+
+// A tile is only worth walking onto if no ghost can get there this many steps after PacMan.
+const SAFETY_STEPS: u32 = 2;
+
+// Blue ghosts are only worth going after while they'll stay blue for this long.
+const HUNT_TICKS: u32 = 90;
+
+/// Steers PacMan toward the nearest remaining dot, the fruit while it's out, or a blue ghost,
+/// keeping out of the way of the others. Stands in for a player when there isn't one.
+///
+/// Ties between equally good routes are broken by a seeded generator, so that otherwise
+/// identical games (the game itself has no randomness between levels) play out differently.
+pub(crate) struct Autopilot {
+    state: u32,
+    // The order routes are tried in, reshuffled each time PacMan reaches a new tile so he
+    // doesn't dither between two equally good ones.
+    directions: [Direction; 4],
+    tile: Option<(u32, u32)>,
+}
+
+impl Default for Autopilot {
+    fn default() -> Self {
+        Self::with_seed(0)
+    }
+}
+
+impl Autopilot {
+    pub fn with_seed(seed: u32) -> Self {
+        // Xorshift gets stuck at zero.
+        Self {
+            state: seed.wrapping_mul(0x9e37_79b9) | 1,
+            directions: [Direction::Up, Direction::Left, Direction::Down, Direction::Right],
+            tile: None,
+        }
+    }
+
+    pub fn steer(&mut self, game: &Game) -> Option<Direction> {
+        let map = game.map();
+        let start = game.pacman().sprite().tile_info().tile();
+        let danger = self.ghost_distances(game);
+        let hunting = game.fright_timer().map_or(false, |fright| fright.ticks_left() > HUNT_TICKS);
+        let prey: Vec<_> = game
+            .ghosts()
+            .iter()
+            .filter(|ghost| hunting && ghost.is_frightened())
+            .map(|ghost| ghost.sprite().tile_info().tile())
+            .collect();
+        if self.tile != Some(start) {
+            self.tile = Some(start);
+            self.shuffle_directions();
+        }
+        let directions = self.directions;
+
+        let goal = explore(game, start, &directions, &danger, SAFETY_STEPS)
+            .into_iter()
+            .find(|&((x, y), first_step)| {
+                let wanted = map.dot(x, y).is_some()
+                    || map.actors_at(x, y).contains(&TileActor::Bonus)
+                    || prey.contains(&(x, y));
+                wanted && first_step.is_some()
+            });
+        if let Some((_, first_step)) = goal {
+            return first_step;
+        }
+
+        // Nothing to eat that can be reached safely: head for whatever PacMan can get to
+        // that's furthest from the ghosts.
+        explore(game, start, &directions, &danger, 0)
+            .into_iter()
+            .max_by_key(|&(tile, _)| danger[index(tile)])
+            .and_then(|(_, first_step)| first_step)
+    }
+
+    // Steps from each tile to the nearest ghost that could catch PacMan.
+    fn ghost_distances(&self, game: &Game) -> Vec<u32> {
+        let map = game.map();
+        let mut distances = vec![u32::MAX; TILES_WIDE * TILES_HIGH];
+        let mut queue = VecDeque::new();
+        for ghost in game.ghosts() {
+            let dangerous = match ghost.state() {
+                GhostState::Roaming | GhostState::Leaving => !ghost.is_frightened(),
+                _ => false,
+            };
+            if dangerous {
+                let tile = ghost.sprite().tile_info().tile();
+                distances[index(tile)] = 0;
+                queue.push_back((tile, Some(ghost.sprite().facing().opposite())));
+            }
+        }
+        // Ghosts can't turn around, so the way behind one is only reached the long way round.
+        while let Some(((x, y), behind)) = queue.pop_front() {
+            let distance = distances[index((x, y))] + 1;
+            for &direction in &[Direction::Up, Direction::Left, Direction::Down, Direction::Right] {
+                if Some(direction) == behind {
+                    continue;
+                }
+                if let Some((tile, next)) = map.get_adjacent(x, y, direction) {
+                    if next.is_valid_path() && distances[index(tile)] > distance {
+                        distances[index(tile)] = distance;
+                        queue.push_back((tile, None));
+                    }
+                }
+            }
+        }
+        distances
+    }
+
+    fn shuffle_directions(&mut self) {
+        for i in (1..self.directions.len()).rev() {
+            let j = self.next() as usize % (i + 1);
+            self.directions.swap(i, j);
+        }
+    }
+
+    fn next(&mut self) -> u32 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 17;
+        self.state ^= self.state << 5;
+        self.state
+    }
+}
+
+fn index((x, y): (u32, u32)) -> usize {
+    y as usize * TILES_WIDE + x as usize
+}
+
+// Breadth-first search outward from PacMan through tiles he'd reach at least `margin` steps
+// before any ghost, nearest first, with the first step taken to get to each.
+fn explore(
+    game: &Game,
+    start: (u32, u32),
+    directions: &[Direction],
+    danger: &[u32],
+    margin: u32,
+) -> Vec<((u32, u32), Option<Direction>)> {
+    let map = game.map();
+    let mut steps = vec![u32::MAX; TILES_WIDE * TILES_HIGH];
+    let mut reached = vec![(start, None)];
+    let mut queue = VecDeque::new();
+    steps[index(start)] = 0;
+    queue.push_back((start, None));
+    while let Some(((x, y), first_step)) = queue.pop_front() {
+        let step = steps[index((x, y))] + 1;
+        for &direction in directions {
+            if let Some((tile, next)) = map.get_adjacent(x, y, direction) {
+                let safe = danger[index(tile)] > step + margin;
+                if next.is_valid_path() && safe && steps[index(tile)] == u32::MAX {
+                    steps[index(tile)] = step;
+                    let first_step = first_step.or(Some(direction));
+                    reached.push((tile, first_step));
+                    queue.push_back((tile, first_step));
+                }
+            }
+        }
+    }
+    reached
+}
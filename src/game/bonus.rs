@@ -0,0 +1,99 @@
+use super::TICKS_PER_SECOND;
+use super::map::TileInformation;
+use super::rng::Prng;
+
+/// Dots eaten at which the level's fruit appears.
+pub(crate) const BONUS_DOTS: [usize; 2] = [70, 170];
+
+/// How many fruit the row along the bottom of the screen holds.
+pub(crate) const FRUIT_HISTORY: usize = 7;
+
+/// Where the fruit sits: just below the ghost house, centered on the door.
+pub(crate) const BONUS_POSITION: (u32, u32, u32, u32) = (16, 15, 0, 4);
+
+// The fruit stays out for somewhere between 9 and 10 seconds.
+const BONUS_MIN_TICKS: u32 = 9 * TICKS_PER_SECOND;
+const BONUS_EXTRA_TICKS: u32 = TICKS_PER_SECOND;
+
+// Fruit sprites sit in a row in the sheet, in level order.
+const FIRST_FRUIT_INDEX: u32 = 44;
+
+/// The bonus fruit offered on each level.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Fruit {
+    Cherries,
+    Strawberry,
+    Peach,
+    Apple,
+    Melon,
+    Galaxian,
+    Bell,
+    Key,
+}
+
+impl Fruit {
+    pub fn points(self) -> u32 {
+        match self {
+            Fruit::Cherries => 100,
+            Fruit::Strawberry => 300,
+            Fruit::Peach => 500,
+            Fruit::Apple => 700,
+            Fruit::Melon => 1000,
+            Fruit::Galaxian => 2000,
+            Fruit::Bell => 3000,
+            Fruit::Key => 5000,
+        }
+    }
+
+    pub fn texture_index(self) -> u32 {
+        FIRST_FRUIT_INDEX + self as u32
+    }
+}
+
+/// A fruit sitting below the ghost house, waiting to be eaten.
+pub(crate) struct Bonus {
+    fruit: Fruit,
+    ticks_left: u32,
+}
+
+impl Bonus {
+    pub fn new(fruit: Fruit, rng: &mut Prng) -> Self {
+        let extra = rng.next() as u32 % (BONUS_EXTRA_TICKS + 1);
+        Self {
+            fruit,
+            ticks_left: BONUS_MIN_TICKS + extra,
+        }
+    }
+
+    pub fn fruit(&self) -> Fruit {
+        self.fruit
+    }
+
+    pub fn tile_info(&self) -> TileInformation {
+        TileInformation::from(BONUS_POSITION)
+    }
+
+    /// Count down, returning `true` once the fruit should disappear.
+    pub fn tick(&mut self) -> bool {
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+        self.ticks_left == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fruit_stays_out_between_nine_and_ten_seconds() {
+        let mut rng = Prng::default();
+        for _ in 0..50 {
+            let mut bonus = Bonus::new(Fruit::Cherries, &mut rng);
+            let mut ticks = 1;
+            while !bonus.tick() {
+                ticks += 1;
+            }
+            assert!(ticks >= BONUS_MIN_TICKS && ticks <= BONUS_MIN_TICKS + BONUS_EXTRA_TICKS);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+pub(crate) const TILE_SIZE: f32 = 8.0;
+
+pub(crate) const TILES_WIDE: usize = 32;
+pub(crate) const TILES_HIGH: usize = 36;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Direction {
+    Up,
+    Left,
+    Right,
+    Down,
+}
+
+impl Direction {
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Left => Direction::Right,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+        }
+    }
+}
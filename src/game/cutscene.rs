@@ -0,0 +1,262 @@
+//! The intermissions played between some levels: short scripted scenes with no maze and no
+//! player, just actors walking across the screen on cue.
+
+use smallvec::SmallVec;
+
+use super::consts::{Direction, TILE_SIZE, TILES_WIDE};
+use super::level::speed;
+use super::map::TileInformation;
+use super::sprite::Sprite;
+
+// Columns in the sprite sheet, for actors drawn across more than one cell.
+const SHEET_COLUMNS: u32 = 14;
+const CELL_SIZE: u32 = 16;
+
+// Blinky flaps his skirt as slowly here as he does in the maze.
+const BLINKY_ANIMATION_TICKS: u32 = 8;
+
+// Everyone walks along the middle row of the screen, coming on at one edge and walking off
+// the other.
+const FLOOR_Y: u32 = 18 * TILE_SIZE as u32 + TILE_SIZE as u32 / 2;
+const LEFT_EDGE: u32 = 0;
+const RIGHT_EDGE: u32 = TILES_WIDE as u32 * TILE_SIZE as u32 - 1;
+// The nail sits just right of center; the cloth caught on it is drawn in the same cell.
+const NAIL_X: u32 = 124;
+
+const PACMAN_LEFT: &[u32] = &[14, 15, 2];
+const BIG_PACMAN_RIGHT: &[u32] = &[16, 18, 20];
+const BLINKY_LEFT: &[u32] = &[58, 59];
+const BLINKY_BLUE: &[u32] = &[64, 65];
+const BLINKY_PATCHED: &[u32] = &[108, 109];
+const BLINKY_NAKED: &[u32] = &[120, 122];
+const NAIL: &[u32] = &[92];
+
+/// Who a cue is for. Each can be on stage at most once at a time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Role {
+    PacMan,
+    Blinky,
+    // The nail, and the bit of Blinky's cloak caught on it.
+    Prop,
+}
+
+impl Role {
+    pub const ALL: [Role; 3] = [Role::PacMan, Role::Blinky, Role::Prop];
+}
+
+enum Action {
+    /// Come on stage at `x` on the floor, `size` sprite sheet cells across and down. Frames
+    /// name the top left cell of each.
+    Enter {
+        x: u32,
+        facing: Direction,
+        speed: u32,
+        frames: &'static [u32],
+        size: (u32, u32),
+    },
+    /// Change speed, as a percentage of full speed.
+    Speed(u32),
+    /// Swap the animation frames.
+    Frames(&'static [u32]),
+}
+
+/// Something that happens `tick` ticks into the scene.
+struct Cue {
+    tick: u32,
+    role: Role,
+    action: Action,
+}
+
+/// A whole scene: its cues in order, and how long it runs.
+pub(crate) struct Script {
+    cues: &'static [Cue],
+    ticks: u32,
+}
+
+const fn enter(
+    tick: u32,
+    role: Role,
+    x: u32,
+    facing: Direction,
+    speed: u32,
+    frames: &'static [u32],
+) -> Cue {
+    Cue { tick, role, action: Action::Enter { x, facing, speed, frames, size: (1, 1) } }
+}
+
+const fn enter_big(
+    tick: u32,
+    role: Role,
+    x: u32,
+    facing: Direction,
+    speed: u32,
+    frames: &'static [u32],
+    size: (u32, u32),
+) -> Cue {
+    Cue { tick, role, action: Action::Enter { x, facing, speed, frames, size } }
+}
+
+const fn set_speed(tick: u32, role: Role, speed: u32) -> Cue {
+    Cue { tick, role, action: Action::Speed(speed) }
+}
+
+const fn set_frames(tick: u32, role: Role, frames: &'static [u32]) -> Cue {
+    Cue { tick, role, action: Action::Frames(frames) }
+}
+
+// Blinky chases PacMan off to the left, then runs back the other way, blue, with a giant
+// PacMan after him.
+static THE_CHASE: Script = Script {
+    cues: &[
+        enter(0, Role::PacMan, RIGHT_EDGE, Direction::Left, 80, PACMAN_LEFT),
+        enter(30, Role::Blinky, RIGHT_EDGE, Direction::Left, 85, BLINKY_LEFT),
+        enter(330, Role::Blinky, LEFT_EDGE, Direction::Right, 60, BLINKY_BLUE),
+        enter_big(378, Role::PacMan, LEFT_EDGE, Direction::Right, 60, BIG_PACMAN_RIGHT, (2, 2)),
+    ],
+    ticks: 760,
+};
+
+// Blinky's cloak snags on a nail as he chases PacMan. It stretches as he pulls, then tears,
+// leaving a scrap behind and his leg showing.
+static THE_SNAG: Script = Script {
+    cues: &[
+        enter(0, Role::Prop, NAIL_X, Direction::Left, 0, NAIL),
+        enter(0, Role::PacMan, RIGHT_EDGE, Direction::Left, 80, PACMAN_LEFT),
+        enter(30, Role::Blinky, RIGHT_EDGE, Direction::Left, 85, BLINKY_LEFT),
+        set_speed(157, Role::Blinky, 10),
+        set_frames(157, Role::Prop, &[93]),
+        set_frames(189, Role::Prop, &[94]),
+        set_frames(205, Role::Prop, &[95]),
+        set_speed(213, Role::Blinky, 0),
+        set_frames(270, Role::Blinky, &[106]),
+        set_frames(270, Role::Prop, &[96]),
+        set_frames(360, Role::Blinky, &[107]),
+    ],
+    ticks: 480,
+};
+
+// Blinky, his cloak stitched up, chases PacMan off to the left, then comes back the other
+// way without it, dragging it behind him.
+static THE_PATCH: Script = Script {
+    cues: &[
+        enter(0, Role::PacMan, RIGHT_EDGE, Direction::Left, 80, PACMAN_LEFT),
+        enter(30, Role::Blinky, RIGHT_EDGE, Direction::Left, 85, BLINKY_PATCHED),
+        enter_big(330, Role::Blinky, LEFT_EDGE, Direction::Right, 50, BLINKY_NAKED, (2, 1)),
+    ],
+    ticks: 780,
+};
+
+/// The intermission played once `level` is cleared, if there is one.
+pub(crate) fn intermission_after(level: u32) -> Option<(u32, &'static Script)> {
+    match level {
+        2 => Some((1, &THE_CHASE)),
+        5 => Some((2, &THE_SNAG)),
+        9 => Some((3, &THE_PATCH)),
+        _ => None,
+    }
+}
+
+/// One of the actors on stage.
+pub(crate) struct CutsceneActor {
+    sprite: Sprite,
+    size: (u32, u32),
+}
+
+impl CutsceneActor {
+    pub fn sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+
+    /// The sprite sheet cells to draw, each with its offset in pixels from the sprite's
+    /// position.
+    pub fn cells(&self) -> SmallVec<[(u32, i32, i32); 4]> {
+        let (columns, rows) = self.size;
+        let first = self.sprite.texture_index();
+        let mut cells = SmallVec::new();
+        for row in 0..rows {
+            for column in 0..columns {
+                let index = first + column + row * SHEET_COLUMNS;
+                let dx = (column * CELL_SIZE) as i32 - ((columns - 1) * CELL_SIZE / 2) as i32;
+                let dy = ((rows - 1) * CELL_SIZE / 2) as i32 - (row * CELL_SIZE) as i32;
+                cells.push((index, dx, dy));
+            }
+        }
+        cells
+    }
+}
+
+/// A script being played out.
+pub(crate) struct Cutscene {
+    script: &'static Script,
+    next_cue: usize,
+    ticks: u32,
+    actors: [Option<CutsceneActor>; 3],
+}
+
+impl Cutscene {
+    pub fn new(script: &'static Script) -> Self {
+        Self {
+            script,
+            next_cue: 0,
+            ticks: 0,
+            actors: [None, None, None],
+        }
+    }
+
+    pub fn actor(&self, role: Role) -> Option<&CutsceneActor> {
+        self.actors[role as usize].as_ref()
+    }
+
+    /// Advance the scene by one tick. Returns true once it's over.
+    pub fn tick(&mut self) -> bool {
+        while let Some(cue) = self.script.cues.get(self.next_cue) {
+            if cue.tick > self.ticks {
+                break;
+            }
+            self.next_cue += 1;
+            self.play(cue);
+        }
+        // Whoever reaches the edge of the screen has walked off it.
+        for slot in self.actors.iter_mut() {
+            let mut walked_off = false;
+            if let Some(actor) = slot.as_mut() {
+                actor.sprite.tick_unchecked(|sprite| {
+                    walked_off = sprite.tile_info().neighbor_pixel(sprite.facing()).is_none();
+                    !walked_off
+                });
+            }
+            if walked_off {
+                *slot = None;
+            }
+        }
+        self.ticks += 1;
+        self.ticks >= self.script.ticks
+    }
+
+    fn play(&mut self, cue: &Cue) {
+        let slot = &mut self.actors[cue.role as usize];
+        match cue.action {
+            Action::Enter { x, facing, speed: percent, frames, size } => {
+                // Taller actors stand on the same floor as everyone else.
+                let y = FLOOR_Y + (size.1 - 1) * CELL_SIZE / 2;
+                let mut sprite =
+                    Sprite::new(TileInformation::from_pixels(x, y), facing, frames.to_vec());
+                if cue.role != Role::PacMan {
+                    sprite = sprite.with_animation_ticks(BLINKY_ANIMATION_TICKS);
+                }
+                sprite.set_speed(speed(percent));
+                *slot = Some(CutsceneActor { sprite, size });
+            },
+            Action::Speed(percent) => {
+                if let Some(actor) = slot.as_mut() {
+                    actor.sprite.set_speed(speed(percent));
+                }
+            },
+            Action::Frames(frames) => {
+                if let Some(actor) = slot.as_mut() {
+                    actor.sprite.set_texture_indexes(frames.to_vec());
+                }
+            },
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Things that happen during a tick, for the renderer (and anything else) to react to.
+
+use super::bonus::Fruit;
+use super::ghost::Personality;
+use super::map::DotKind;
+use super::mode::GhostMode;
+
+/// PacMan cleared a dot or energizer from the map.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct DotEaten {
+    pub tile: (u32, u32),
+    pub kind: DotKind,
+}
+
+/// The ghosts switched between scatter and chase, reversing as they did.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct ModeChanged {
+    pub mode: GhostMode,
+}
+
+/// An energizer turned the ghosts blue for `ticks` ticks.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Frightened {
+    pub ticks: u32,
+}
+
+/// The energizer wore off and any ghosts still blue went back to normal.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct FrightOver;
+
+/// A ghost caught PacMan.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct PacManCaught {
+    pub ghost: Personality,
+    pub tile: (u32, u32),
+}
+
+/// PacMan caught a frightened ghost, which is now heading home as eyes. The game freezes
+/// for a moment while `points` is shown where it happened.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct GhostEaten {
+    pub ghost: Personality,
+    pub tile: (u32, u32),
+    pub points: u32,
+}
+
+/// PacMan's death animation is starting.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct PacManDying;
+
+/// The death sequence finished and a life was used up; `lives` are left.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct LifeLost {
+    pub lives: u32,
+}
+
+/// No lives left.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct GameOver;
+
+/// The score crossed the extra life threshold.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct ExtraLife;
+
+/// The last dot on `level` was eaten; the maze is about to flash.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct LevelComplete {
+    pub level: u32,
+}
+
+/// The maze is cleared and intermission `number` (1 to 3) is playing before the next level.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct IntermissionStarted {
+    pub number: u32,
+}
+
+/// A fresh board for `level` is set up and play is about to begin.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct LevelStarted {
+    pub level: u32,
+}
+
+/// The level's fruit appeared below the ghost house.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct BonusShown {
+    pub fruit: Fruit,
+}
+
+/// PacMan ate the fruit, worth `points`.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct BonusEaten {
+    pub fruit: Fruit,
+    pub tile: (u32, u32),
+    pub points: u32,
+}
+
+/// Nobody ate the fruit in time and it's gone.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct BonusExpired;
+
+#[derive(Copy, Clone, Debug)]
+pub(crate) enum Event {
+    DotEaten(DotEaten),
+    ModeChanged(ModeChanged),
+    Frightened(Frightened),
+    FrightOver(FrightOver),
+    PacManCaught(PacManCaught),
+    GhostEaten(GhostEaten),
+    PacManDying(PacManDying),
+    LifeLost(LifeLost),
+    GameOver(GameOver),
+    ExtraLife(ExtraLife),
+    LevelComplete(LevelComplete),
+    IntermissionStarted(IntermissionStarted),
+    LevelStarted(LevelStarted),
+    BonusShown(BonusShown),
+    BonusEaten(BonusEaten),
+    BonusExpired(BonusExpired),
+}
@@ -0,0 +1,503 @@
+use super::consts::Direction;
+use super::level::speed;
+use super::map::{Map, TileInformation};
+use super::mode::GhostMode;
+use super::quirks::Quirks;
+use super::rng::Prng;
+use super::sprite::Sprite;
+use super::targeting::{self, Board, Targeting, distance_squared};
+
+// Ghosts flap their skirts more slowly than PacMan chomps.
+const GHOST_ANIMATION_TICKS: u32 = 8;
+
+// When two directions are equally good, the arcade prefers them in this order. It also falls
+// back through them, from its random pick, when a frightened ghost's pick is blocked.
+const TIE_BREAK_ORDER: [Direction; 4] = [
+    Direction::Up,
+    Direction::Left,
+    Direction::Down,
+    Direction::Right,
+];
+
+// Ghosts dawdle inside the house; eyes race back to it.
+const HOUSE_SPEED_PERCENT: u32 = 50;
+const EYES_SPEED_PERCENT: u32 = 200;
+
+// Where eyes aim for: the tile just above the right half of the door.
+const HOUSE_DOOR_TARGET: (i32, i32) = (16, 21);
+
+// Key points of the ghost house, in pixels: the line down its middle, the height ghosts
+// bob around (and how far), and the spot just above the door where they come out.
+const HOUSE_CENTER_X: u32 = 16 * 8;
+const HOUSE_BOUNCE_Y: u32 = 18 * 8;
+const HOUSE_BOUNCE_HEIGHT: u32 = 4;
+const HOUSE_EXIT_Y: u32 = 21 * 8 + 4;
+
+// Blue and white frightened ghosts, two frames each.
+const FRIGHTENED_INDEXES: [u32; 2] = [64, 65];
+const FLASHING_INDEXES: [u32; 2] = [66, 67];
+
+// Eyes have one frame per direction: right, left, up, down.
+const EYES_INDEX: u32 = 78;
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Personality {
+    Blinky,
+    Pinky,
+    Inky,
+    Clyde,
+}
+
+impl Personality {
+    pub const ALL: [Personality; 4] = [
+        Personality::Blinky,
+        Personality::Pinky,
+        Personality::Inky,
+        Personality::Clyde,
+    ];
+
+    /// The corner each ghost retreats to, just outside the maze.
+    pub fn scatter_target(self) -> (i32, i32) {
+        match self {
+            Personality::Blinky => (27, 35),
+            Personality::Pinky => (4, 35),
+            Personality::Inky => (29, 0),
+            Personality::Clyde => (2, 0),
+        }
+    }
+}
+
+/// Where a ghost is in its life cycle.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum GhostState {
+    /// Bobbing up and down inside the ghost house, waiting to be let out.
+    Home,
+    /// Making its way out through the door.
+    Leaving,
+    /// Out in the maze.
+    Roaming,
+    /// Eaten: just a pair of eyes heading back to the door.
+    Eaten,
+    /// Eyes dropping through the door to be revived.
+    Entering,
+}
+
+/// How a ghost is drawn, apart from which way it faces.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Look {
+    Normal,
+    Blue,
+    White,
+    Eyes,
+}
+
+pub(crate) struct Ghost {
+    personality: Personality,
+    sprite: Sprite,
+    targeting: Box<dyn Targeting>,
+    state: GhostState,
+    look: Look,
+}
+
+impl Ghost {
+    pub fn new(personality: Personality, quirks: &Quirks) -> Self {
+        let (start, facing) = match personality {
+            // Just above the door of the ghost house.
+            Personality::Blinky => (TileInformation::from((16, 21, 0, 4)), Direction::Left),
+            // The rest start inside it: Pinky in the middle, Inky left, Clyde right.
+            Personality::Pinky => (TileInformation::from((16, 18, 0, 0)), Direction::Down),
+            Personality::Inky => (TileInformation::from((14, 18, 0, 0)), Direction::Up),
+            Personality::Clyde => (TileInformation::from((18, 18, 0, 0)), Direction::Up),
+        };
+        Self {
+            personality,
+            sprite: Sprite::new(start, facing, texture_indexes(personality, Look::Normal, facing))
+                .with_animation_ticks(GHOST_ANIMATION_TICKS),
+            targeting: targeting::chase_strategy(personality, quirks),
+            state: if personality == Personality::Blinky {
+                GhostState::Roaming
+            } else {
+                GhostState::Home
+            },
+            look: Look::Normal,
+        }
+    }
+
+    pub fn personality(&self) -> Personality {
+        self.personality
+    }
+
+    pub fn sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+
+    /// Put the ghost somewhere in particular, in any state.
+    #[cfg(test)]
+    pub fn place(&mut self, sprite: Sprite, state: GhostState) {
+        self.sprite = sprite;
+        self.state = state;
+    }
+
+    pub fn state(&self) -> GhostState {
+        self.state
+    }
+
+    pub fn is_home(&self) -> bool {
+        self.state == GhostState::Home
+    }
+
+    /// Let the ghost out of the house.
+    pub fn release(&mut self) {
+        if self.state == GhostState::Home {
+            self.state = GhostState::Leaving;
+        }
+    }
+
+    pub fn is_frightened(&self) -> bool {
+        self.look == Look::Blue || self.look == Look::White
+    }
+
+    /// Eyes on their way home (or just arriving) can't be frightened or eaten.
+    pub fn is_eyes(&self) -> bool {
+        self.look == Look::Eyes
+    }
+
+    /// An energizer was eaten: turn blue and turn around.
+    pub fn frighten(&mut self) {
+        if self.is_eyes() {
+            return;
+        }
+        self.set_look(Look::Blue);
+        self.reverse();
+    }
+
+    /// PacMan caught this ghost while it was frightened.
+    pub fn eat(&mut self) {
+        // One caught on its way out is still over the door, so it can drop straight back in.
+        self.state = match self.state {
+            GhostState::Leaving => GhostState::Entering,
+            _ => GhostState::Eaten,
+        };
+        self.set_look(Look::Eyes);
+    }
+
+    /// Flash white (or back to blue) as the fright wears off.
+    pub fn flash(&mut self, white: bool) {
+        if self.is_frightened() {
+            self.set_look(if white { Look::White } else { Look::Blue });
+        }
+    }
+
+    /// The fright is over.
+    pub fn calm(&mut self) {
+        if self.is_frightened() {
+            self.set_look(Look::Normal);
+        }
+    }
+
+    fn set_look(&mut self, look: Look) {
+        self.look = look;
+        let indexes = texture_indexes(self.personality, look, self.sprite.facing());
+        self.sprite.set_texture_indexes(indexes);
+    }
+
+    /// The tile this ghost is heading for in the given mode.
+    pub fn target(&self, mode: GhostMode, pacman: &Sprite, blinky: &Sprite) -> (i32, i32) {
+        if self.state == GhostState::Eaten {
+            return HOUSE_DOOR_TARGET;
+        }
+        match mode {
+            GhostMode::Scatter => self.personality.scatter_target(),
+            GhostMode::Chase => self.targeting.target(&Board {
+                ghost: &self.sprite,
+                pacman,
+                blinky,
+            }),
+        }
+    }
+
+    /// Turn around on the spot, as every ghost does when the mode changes.
+    pub fn reverse(&mut self) {
+        if self.state != GhostState::Roaming {
+            return;
+        }
+        let facing = self.sprite.facing().opposite();
+        let speed = self.sprite.speed();
+        self.sprite.update(speed, facing, texture_indexes(self.personality, self.look, facing));
+    }
+
+    pub fn hold(&mut self) {
+        self.sprite.hold();
+    }
+
+    /// Move for one tick, heading for `target` at each tile center, or wandering at random
+    /// when there is no target (frightened).
+    pub fn tick(&mut self, map: &Map, target: Option<(i32, i32)>, speed: u32, rng: &mut Prng) {
+        match self.state {
+            GhostState::Home => self.bounce(),
+            GhostState::Leaving => self.leave(),
+            GhostState::Roaming => self.roam(map, target, speed, rng),
+            GhostState::Eaten => self.return_home(map),
+            GhostState::Entering => self.enter(),
+        }
+    }
+
+    fn roam(&mut self, map: &Map, target: Option<(i32, i32)>, speed: u32, rng: &mut Prng) {
+        let personality = self.personality;
+        let look = self.look;
+        self.sprite.set_speed(speed);
+        self.sprite.tick_steered(map, |sprite| {
+            let tile_info = sprite.tile_info();
+            if tile_info.is_centered() {
+                let (x, y) = tile_info.tile();
+                let direction = match target {
+                    Some(target) => {
+                        let restricted = map.get_tile(x, y).restricted_directions();
+                        choose_direction(map, (x, y), sprite.facing(), target, restricted)
+                    },
+                    None => wander(map, (x, y), sprite.facing(), rng),
+                };
+                sprite.update(speed, direction, texture_indexes(personality, look, direction));
+            }
+            true
+        });
+    }
+
+    // Eyes find their way back like any other ghost, but stop once they're over the door.
+    fn return_home(&mut self, map: &Map) {
+        let personality = self.personality;
+        let look = self.look;
+        let speed = speed(EYES_SPEED_PERCENT);
+        let mut at_door = false;
+        self.sprite.set_speed(speed);
+        self.sprite.tick_steered(map, |sprite| {
+            let tile_info = sprite.tile_info();
+            if !tile_info.is_centered() {
+                return true;
+            }
+            let (x, y) = tile_info.tile();
+            at_door = map
+                .get_adjacent(x, y, Direction::Down)
+                .map_or(false, |(_, below)| below.is_ghost_door());
+            if at_door {
+                return false;
+            }
+            let direction = choose_direction(map, (x, y), sprite.facing(), HOUSE_DOOR_TARGET, &[]);
+            sprite.update(speed, direction, texture_indexes(personality, look, direction));
+            true
+        });
+        if at_door {
+            self.state = GhostState::Entering;
+        }
+    }
+
+    // Over to the middle of the door, down into the house, across to this ghost's own spot,
+    // and then back out again as good as new.
+    fn enter(&mut self) {
+        let personality = self.personality;
+        let look = self.look;
+        let speed = speed(EYES_SPEED_PERCENT);
+        let home_x = house_x(personality);
+        self.sprite.set_speed(speed);
+        self.sprite.tick_unchecked(|sprite| {
+            let (x, y) = sprite.tile_info().pixels();
+            let facing = if y > HOUSE_BOUNCE_Y && x < HOUSE_CENTER_X {
+                Direction::Right
+            } else if y > HOUSE_BOUNCE_Y && x > HOUSE_CENTER_X {
+                Direction::Left
+            } else if y > HOUSE_BOUNCE_Y {
+                Direction::Down
+            } else if x < home_x {
+                Direction::Right
+            } else if x > home_x {
+                Direction::Left
+            } else {
+                return false;
+            };
+            sprite.update(speed, facing, texture_indexes(personality, look, facing));
+            true
+        });
+        if self.sprite.tile_info().pixels() == (home_x, HOUSE_BOUNCE_Y) {
+            self.state = GhostState::Leaving;
+            self.set_look(Look::Normal);
+        }
+    }
+
+    fn bounce(&mut self) {
+        let personality = self.personality;
+        let look = self.look;
+        let speed = speed(HOUSE_SPEED_PERCENT);
+        self.sprite.set_speed(speed);
+        self.sprite.tick_unchecked(|sprite| {
+            let (_, y) = sprite.tile_info().pixels();
+            let facing = match sprite.facing() {
+                Direction::Up if y >= HOUSE_BOUNCE_Y + HOUSE_BOUNCE_HEIGHT => Direction::Down,
+                Direction::Down if y <= HOUSE_BOUNCE_Y - HOUSE_BOUNCE_HEIGHT => Direction::Up,
+                Direction::Up | Direction::Down => sprite.facing(),
+                Direction::Left | Direction::Right => Direction::Up,
+            };
+            sprite.update(speed, facing, texture_indexes(personality, look, facing));
+            true
+        });
+    }
+
+    // Back to the middle height, across to the middle, then straight up through the door.
+    fn leave(&mut self) {
+        let personality = self.personality;
+        let look = self.look;
+        let speed = speed(HOUSE_SPEED_PERCENT);
+        self.sprite.set_speed(speed);
+        self.sprite.tick_unchecked(|sprite| {
+            let (x, y) = sprite.tile_info().pixels();
+            let facing = if x != HOUSE_CENTER_X && y > HOUSE_BOUNCE_Y {
+                Direction::Down
+            } else if x != HOUSE_CENTER_X && y < HOUSE_BOUNCE_Y {
+                Direction::Up
+            } else if x < HOUSE_CENTER_X {
+                Direction::Right
+            } else if x > HOUSE_CENTER_X {
+                Direction::Left
+            } else if y < HOUSE_EXIT_Y {
+                Direction::Up
+            } else {
+                return false;
+            };
+            sprite.update(speed, facing, texture_indexes(personality, look, facing));
+            true
+        });
+        if self.sprite.tile_info().pixels() == (HOUSE_CENTER_X, HOUSE_EXIT_Y) {
+            self.state = GhostState::Roaming;
+            let facing = Direction::Left;
+            self.sprite.update(speed, facing, texture_indexes(personality, look, facing));
+        }
+    }
+}
+
+/// Pick the exit from a tile that lands closest (as the crow flies) to the target, other than
+/// any `restricted` ones. Ghosts never double back on themselves unless there's no other way
+/// out.
+fn choose_direction(
+    map: &Map,
+    (x, y): (u32, u32),
+    facing: Direction,
+    target: (i32, i32),
+    restricted: &[Direction],
+) -> Direction {
+    let mut best: Option<(i32, Direction)> = None;
+    for &direction in TIE_BREAK_ORDER.iter() {
+        if direction == facing.opposite() || restricted.contains(&direction) {
+            continue;
+        }
+        if let Some((tile, next)) = map.get_adjacent(x, y, direction) {
+            if !next.is_valid_path() {
+                continue;
+            }
+            let distance = distance_squared((tile.0 as i32, tile.1 as i32), target);
+            if best.map_or(true, |(best_distance, _)| distance < best_distance) {
+                best = Some((distance, direction));
+            }
+        }
+    }
+    best.map_or(facing.opposite(), |(_, direction)| direction)
+}
+
+/// Pick a random exit from a tile, other than going back the way we came.
+fn wander(
+    map: &Map,
+    (x, y): (u32, u32),
+    facing: Direction,
+    rng: &mut Prng,
+) -> Direction {
+    let first = rng.next_direction_bits() as usize;
+    for i in 0..TIE_BREAK_ORDER.len() {
+        let direction = TIE_BREAK_ORDER[(first + i) % TIE_BREAK_ORDER.len()];
+        if direction == facing.opposite() {
+            continue;
+        }
+        let open = map
+            .get_adjacent(x, y, direction)
+            .map_or(false, |(_, next)| next.is_valid_path());
+        if open {
+            return direction;
+        }
+    }
+    facing.opposite()
+}
+
+// Where each ghost sits inside the house, in pixels across. Blinky takes the middle.
+fn house_x(personality: Personality) -> u32 {
+    match personality {
+        Personality::Blinky | Personality::Pinky => HOUSE_CENTER_X,
+        Personality::Inky => HOUSE_CENTER_X - 16,
+        Personality::Clyde => HOUSE_CENTER_X + 16,
+    }
+}
+
+fn texture_indexes(personality: Personality, look: Look, facing: Direction) -> Vec<u32> {
+    match look {
+        Look::Blue => return FRIGHTENED_INDEXES.to_vec(),
+        Look::White => return FLASHING_INDEXES.to_vec(),
+        Look::Eyes => return vec![EYES_INDEX + match facing {
+            Direction::Right => 0,
+            Direction::Left => 1,
+            Direction::Up => 2,
+            Direction::Down => 3,
+        }],
+        Look::Normal => {},
+    }
+    // Each ghost's row in the sprite sheet: two frames each for right, left, up, down.
+    let row_start = match personality {
+        Personality::Blinky => 56,
+        Personality::Pinky => 70,
+        Personality::Inky => 84,
+        Personality::Clyde => 98,
+    };
+    let first = row_start + match facing {
+        Direction::Right => 0,
+        Direction::Left => 2,
+        Direction::Up => 4,
+        Direction::Down => 6,
+    };
+    vec![first, first + 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::map::NO_UP_TILES;
+
+    // An ordinary four-way junction in the top left of the maze.
+    const JUNCTION: (u32, u32) = (8, 27);
+
+    fn turn_at_junction(facing: Direction, target: (i32, i32)) -> Direction {
+        choose_direction(&Map::default(), JUNCTION, facing, target, &[])
+    }
+
+    #[test]
+    fn ties_break_up_then_left_then_down() {
+        // Up and right are each a tile from the target.
+        assert_eq!(turn_at_junction(Direction::Right, (9, 28)), Direction::Up);
+        // Left and down.
+        assert_eq!(turn_at_junction(Direction::Down, (7, 26)), Direction::Left);
+        // Down and right.
+        assert_eq!(turn_at_junction(Direction::Down, (9, 26)), Direction::Down);
+    }
+
+    #[test]
+    fn never_turns_around() {
+        // The target is straight back the way the ghost came.
+        assert_eq!(turn_at_junction(Direction::Right, (3, 27)), Direction::Up);
+    }
+
+    #[test]
+    fn no_up_tiles_block_turning_up() {
+        let map = Map::default();
+        for &(x, y) in NO_UP_TILES {
+            let above = (x as i32, 35);
+            let facing = Direction::Left;
+            assert_eq!(choose_direction(&map, (x, y), facing, above, &[]), Direction::Up);
+            let restricted = &[Direction::Up];
+            assert_eq!(choose_direction(&map, (x, y), facing, above, restricted), Direction::Left);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Deciding when ghosts get let out of the ghost house, as in the arcade.
+//!
+//! Normally each ghost waiting inside has a personal dot counter, and only the first one
+//! in line (Pinky, then Inky, then Clyde) counts the dots PacMan eats; it leaves once it
+//! reaches the level's limit. After PacMan loses a life a single global counter is used
+//! instead. Either way, if PacMan goes too long without eating, the next ghost in line is
+//! let out regardless.
+
+use super::ghost::{Ghost, Personality};
+use super::level::Level;
+
+// Global counter values at which each ghost leaves after a life is lost.
+const GLOBAL_PINKY_LIMIT: u32 = 7;
+const GLOBAL_INKY_LIMIT: u32 = 17;
+const GLOBAL_CLYDE_LIMIT: u32 = 32;
+
+#[derive(Default)]
+pub(crate) struct GhostHouse {
+    // Indexed by `Personality as usize`.
+    personal: [u32; 4],
+    global: Option<u32>,
+    idle_ticks: u32,
+}
+
+impl GhostHouse {
+    /// PacMan lost a life: switch to the global counter until Clyde is out.
+    pub fn use_global_counter(&mut self) {
+        self.global = Some(0);
+        self.idle_ticks = 0;
+    }
+
+    /// Run once per tick, with whether PacMan ate something this tick. Releases ghosts
+    /// whose time has come.
+    pub fn tick(&mut self, ghosts: &mut [Ghost], level: &Level, dot_eaten: bool) {
+        if dot_eaten {
+            self.idle_ticks = 0;
+            if let Some(global) = self.global.as_mut() {
+                *global += 1;
+            } else if let Some(ghost) = ghosts.iter().find(|ghost| ghost.is_home()) {
+                self.personal[ghost.personality() as usize] += 1;
+            }
+        } else {
+            self.idle_ticks += 1;
+        }
+
+        if let Some(global) = self.global {
+            for ghost in ghosts.iter_mut().filter(|ghost| ghost.is_home()) {
+                match (ghost.personality(), global) {
+                    (Personality::Pinky, GLOBAL_PINKY_LIMIT) |
+                    (Personality::Inky, GLOBAL_INKY_LIMIT) => ghost.release(),
+                    // Clyde isn't let out by the global counter; it just switches back to
+                    // the personal counters.
+                    (Personality::Clyde, GLOBAL_CLYDE_LIMIT) => self.global = None,
+                    _ => {},
+                }
+            }
+        }
+
+        let personal = &self.personal;
+        let global = self.global;
+        let idle = self.idle_ticks >= level.house_idle_ticks();
+        if let Some(ghost) = ghosts.iter_mut().find(|ghost| ghost.is_home()) {
+            let personality = ghost.personality();
+            let counted_out = global.is_none()
+                && personal[personality as usize] >= level.house_dot_limit(personality);
+            if counted_out || idle {
+                ghost.release();
+                if idle {
+                    self.idle_ticks = 0;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::quirks::Quirks;
+
+    fn ghosts() -> Vec<Ghost> {
+        let quirks = Quirks::default();
+        Personality::ALL.iter().map(|&personality| Ghost::new(personality, &quirks)).collect()
+    }
+
+    fn home(ghosts: &[Ghost]) -> Vec<Personality> {
+        ghosts.iter().filter(|ghost| ghost.is_home()).map(|ghost| ghost.personality()).collect()
+    }
+
+    #[test]
+    fn personal_counters_release_in_turn() {
+        let level = Level::default();
+        let mut house = GhostHouse::default();
+        let mut ghosts = ghosts();
+        house.tick(&mut ghosts, &level, false);
+        assert_eq!(home(&ghosts), [Personality::Inky, Personality::Clyde]);
+
+        let inky = level.house_dot_limit(Personality::Inky);
+        for _ in 1..inky {
+            house.tick(&mut ghosts, &level, true);
+        }
+        assert_eq!(home(&ghosts), [Personality::Inky, Personality::Clyde]);
+        house.tick(&mut ghosts, &level, true);
+        assert_eq!(home(&ghosts), [Personality::Clyde]);
+
+        // Clyde only starts counting once Inky is out.
+        let clyde = level.house_dot_limit(Personality::Clyde);
+        for _ in 1..clyde {
+            house.tick(&mut ghosts, &level, true);
+        }
+        assert_eq!(home(&ghosts), [Personality::Clyde]);
+        house.tick(&mut ghosts, &level, true);
+        assert!(home(&ghosts).is_empty());
+    }
+
+    #[test]
+    fn global_counter_releases_pinky_and_inky() {
+        let level = Level::default();
+        let mut house = GhostHouse::default();
+        let mut ghosts = ghosts();
+        house.use_global_counter();
+        for dots in 1..=GLOBAL_CLYDE_LIMIT {
+            house.tick(&mut ghosts, &level, true);
+            let expected: &[Personality] = match dots {
+                _ if dots < GLOBAL_PINKY_LIMIT =>
+                    &[Personality::Pinky, Personality::Inky, Personality::Clyde],
+                _ if dots < GLOBAL_INKY_LIMIT => &[Personality::Inky, Personality::Clyde],
+                _ => &[Personality::Clyde],
+            };
+            assert_eq!(home(&ghosts), expected, "after {} dots", dots);
+        }
+        // Back on the personal counters, Clyde has all of his own to count.
+        let clyde = level.house_dot_limit(Personality::Clyde);
+        for _ in 1..clyde {
+            house.tick(&mut ghosts, &level, true);
+        }
+        assert_eq!(home(&ghosts), [Personality::Clyde]);
+        house.tick(&mut ghosts, &level, true);
+        assert!(home(&ghosts).is_empty());
+    }
+
+    #[test]
+    fn idling_releases_the_next_ghost() {
+        let level = Level::default();
+        let mut house = GhostHouse::default();
+        let mut ghosts = ghosts();
+        for _ in 1..level.house_idle_ticks() {
+            house.tick(&mut ghosts, &level, false);
+        }
+        assert_eq!(home(&ghosts), [Personality::Inky, Personality::Clyde]);
+        house.tick(&mut ghosts, &level, false);
+        assert_eq!(home(&ghosts), [Personality::Clyde]);
+
+        // Eating a dot starts the wait over.
+        for _ in 1..level.house_idle_ticks() {
+            house.tick(&mut ghosts, &level, false);
+        }
+        house.tick(&mut ghosts, &level, true);
+        house.tick(&mut ghosts, &level, false);
+        assert_eq!(home(&ghosts), [Personality::Clyde]);
+    }
+}
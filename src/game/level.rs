@@ -0,0 +1,288 @@
+use super::bonus::Fruit;
+use super::ghost::Personality;
+use super::TICKS_PER_SECOND;
+
+/// 100% speed in the arcade (about 75.76 pixels per second), in 1/256ths of a pixel per tick.
+pub(crate) const FULL_SPEED: u32 = 323;
+
+/// Convert a percentage of full speed into 1/256ths of a pixel per tick.
+pub(crate) fn speed(percent: u32) -> u32 {
+    FULL_SPEED * percent / 100
+}
+
+/// The level currently being played, starting at 1.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Level {
+    number: u32,
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self { number: 1 }
+    }
+}
+
+/// Everything that changes from one level to the next.
+pub(crate) struct LevelSpec {
+    pub fruit: Fruit,
+    pub speeds: &'static LevelSpeeds,
+    /// Blinky's two Cruise Elroy stages, from the first (fewest dots eaten) on.
+    pub elroy: [ElroyStage; 2],
+    pub fright: FrightTime,
+    /// How long each scatter/chase phase lasts, in ticks, starting with scatter. Once the
+    /// list runs out the ghosts chase forever.
+    pub mode_schedule: &'static [u32],
+    /// Dots each ghost counts before leaving the house, indexed by `Personality as usize`.
+    pub house_dot_limits: [u32; 4],
+    /// How long PacMan can go without eating before a ghost is let out anyway.
+    pub house_idle_ticks: u32,
+}
+
+/// Speeds as percentages of FULL_SPEED.
+pub(crate) struct LevelSpeeds {
+    pub pacman: u32,
+    pub ghost: u32,
+    pub pacman_frightened: u32,
+    pub ghost_frightened: u32,
+}
+
+/// Blinky speeds up to `speed` percent once only `dots_left` dots remain.
+pub(crate) struct ElroyStage {
+    pub dots_left: u32,
+    pub speed: u32,
+}
+
+/// How long an energizer keeps the ghosts blue, and how many times they flash white as a
+/// warning before it wears off.
+pub(crate) struct FrightTime {
+    pub ticks: u32,
+    pub flashes: u32,
+}
+
+const fn seconds(seconds: u32) -> u32 {
+    seconds * TICKS_PER_SECOND
+}
+
+const fn fright(seconds: u32, flashes: u32) -> FrightTime {
+    FrightTime { ticks: seconds * TICKS_PER_SECOND, flashes }
+}
+
+const fn elroy(
+    dots_left: u32,
+    speed: u32,
+    second_dots_left: u32,
+    second_speed: u32,
+) -> [ElroyStage; 2] {
+    [
+        ElroyStage { dots_left, speed },
+        ElroyStage { dots_left: second_dots_left, speed: second_speed },
+    ]
+}
+
+static SPEEDS_1: LevelSpeeds =
+    LevelSpeeds { pacman: 80, ghost: 75, pacman_frightened: 90, ghost_frightened: 50 };
+static SPEEDS_2_TO_4: LevelSpeeds =
+    LevelSpeeds { pacman: 90, ghost: 85, pacman_frightened: 95, ghost_frightened: 55 };
+static SPEEDS_5_TO_20: LevelSpeeds =
+    LevelSpeeds { pacman: 100, ghost: 95, pacman_frightened: 100, ghost_frightened: 60 };
+// Ghosts no longer get frightened from here on.
+static SPEEDS_21_ON: LevelSpeeds =
+    LevelSpeeds { pacman: 90, ghost: 95, pacman_frightened: 90, ghost_frightened: 60 };
+
+// The one-tick scatters on later levels are real: ghosts reverse twice in quick succession.
+static SCHEDULE_1: &[u32] =
+    &[seconds(7), seconds(20), seconds(7), seconds(20), seconds(5), seconds(20), seconds(5)];
+static SCHEDULE_2_TO_4: &[u32] =
+    &[seconds(7), seconds(20), seconds(7), seconds(20), seconds(5), seconds(1033), 1];
+static SCHEDULE_5_ON: &[u32] =
+    &[seconds(5), seconds(20), seconds(5), seconds(20), seconds(5), seconds(1037), 1];
+
+const fn spec(
+    fruit: Fruit,
+    speeds: &'static LevelSpeeds,
+    elroy: [ElroyStage; 2],
+    fright: FrightTime,
+    mode_schedule: &'static [u32],
+    house_dot_limits: [u32; 4],
+    house_idle_seconds: u32,
+) -> LevelSpec {
+    LevelSpec {
+        fruit,
+        speeds,
+        elroy,
+        fright,
+        mode_schedule,
+        house_dot_limits,
+        house_idle_ticks: seconds(house_idle_seconds),
+    }
+}
+
+// One row per level from 1; every level past the end plays like the last row. Columns:
+// fruit, speeds, Elroy (dots left, speed) for both stages, fright (seconds, flashes),
+// scatter/chase schedule, house dot limits (Blinky, Pinky, Inky, Clyde), house idle seconds.
+static LEVEL_TABLE: &[LevelSpec] = &[
+    spec(Fruit::Cherries, &SPEEDS_1, elroy(20, 80, 10, 85), fright(6, 5), SCHEDULE_1, [0, 0, 30, 60], 4),
+    spec(Fruit::Strawberry, &SPEEDS_2_TO_4, elroy(30, 90, 15, 95), fright(5, 5), SCHEDULE_2_TO_4, [0, 0, 0, 50], 4),
+    spec(Fruit::Peach, &SPEEDS_2_TO_4, elroy(40, 90, 20, 95), fright(4, 5), SCHEDULE_2_TO_4, [0, 0, 0, 0], 4),
+    spec(Fruit::Peach, &SPEEDS_2_TO_4, elroy(40, 90, 20, 95), fright(3, 5), SCHEDULE_2_TO_4, [0, 0, 0, 0], 4),
+    spec(Fruit::Apple, &SPEEDS_5_TO_20, elroy(40, 100, 20, 105), fright(2, 5), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Apple, &SPEEDS_5_TO_20, elroy(50, 100, 25, 105), fright(5, 5), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Melon, &SPEEDS_5_TO_20, elroy(50, 100, 25, 105), fright(2, 5), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Melon, &SPEEDS_5_TO_20, elroy(50, 100, 25, 105), fright(2, 5), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Galaxian, &SPEEDS_5_TO_20, elroy(60, 100, 30, 105), fright(1, 3), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Galaxian, &SPEEDS_5_TO_20, elroy(60, 100, 30, 105), fright(5, 5), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Bell, &SPEEDS_5_TO_20, elroy(60, 100, 30, 105), fright(2, 5), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Bell, &SPEEDS_5_TO_20, elroy(80, 100, 40, 105), fright(1, 3), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_5_TO_20, elroy(80, 100, 40, 105), fright(1, 3), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_5_TO_20, elroy(80, 100, 40, 105), fright(3, 5), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_5_TO_20, elroy(100, 100, 50, 105), fright(1, 3), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_5_TO_20, elroy(100, 100, 50, 105), fright(1, 3), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_5_TO_20, elroy(100, 100, 50, 105), fright(0, 0), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_5_TO_20, elroy(100, 100, 50, 105), fright(1, 3), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_5_TO_20, elroy(120, 100, 60, 105), fright(0, 0), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_5_TO_20, elroy(120, 100, 60, 105), fright(0, 0), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+    spec(Fruit::Key, &SPEEDS_21_ON, elroy(120, 100, 60, 105), fright(0, 0), SCHEDULE_5_ON, [0, 0, 0, 0], 3),
+];
+
+impl Level {
+    pub fn number(&self) -> u32 {
+        self.number
+    }
+
+    /// The level after this one.
+    pub fn next(&self) -> Self {
+        Self { number: self.number + 1 }
+    }
+
+    pub fn spec(&self) -> &'static LevelSpec {
+        let row = (self.number.max(1) as usize - 1).min(LEVEL_TABLE.len() - 1);
+        &LEVEL_TABLE[row]
+    }
+
+    pub fn fruit(&self) -> Fruit {
+        self.spec().fruit
+    }
+
+    /// The fruit of this level and up to `count - 1` before it, newest first, for the row
+    /// along the bottom of the screen.
+    pub fn fruit_history(&self, count: usize) -> impl Iterator<Item = Fruit> {
+        let newest = self.number.max(1);
+        let oldest = newest.saturating_sub(count as u32 - 1).max(1);
+        (oldest..=newest).rev().map(|number| Level { number }.fruit())
+    }
+
+    pub fn speeds(&self) -> &'static LevelSpeeds {
+        self.spec().speeds
+    }
+
+    /// Blinky's Cruise Elroy stage with `dots_left` dots on the board, if he's reached one.
+    pub fn elroy(&self, dots_left: usize) -> Option<&'static ElroyStage> {
+        self.spec()
+            .elroy
+            .iter()
+            .rev()
+            .find(|stage| dots_left <= stage.dots_left as usize)
+    }
+
+    pub fn fright_time(&self) -> &'static FrightTime {
+        &self.spec().fright
+    }
+
+    /// Dots the ghost has to count before it leaves the ghost house. Blinky starts outside.
+    pub fn house_dot_limit(&self, personality: Personality) -> u32 {
+        self.spec().house_dot_limits[personality as usize]
+    }
+
+    pub fn house_idle_ticks(&self) -> u32 {
+        self.spec().house_idle_ticks
+    }
+
+    pub fn mode_schedule(&self) -> &'static [u32] {
+        self.spec().mode_schedule
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ptr;
+
+    fn level(number: u32) -> Level {
+        Level { number }
+    }
+
+    #[test]
+    fn first_level_plays_like_the_arcade() {
+        let level = Level::default();
+        assert_eq!(level.number(), 1);
+        assert_eq!(level.speeds().pacman, 80);
+        assert_eq!(level.speeds().ghost, 75);
+        assert_eq!(level.fright_time().ticks, seconds(6));
+        assert_eq!(level.fright_time().flashes, 5);
+        assert_eq!(level.mode_schedule()[0], seconds(7));
+        assert_eq!(level.house_dot_limit(Personality::Inky), 30);
+        assert_eq!(level.house_dot_limit(Personality::Clyde), 60);
+        assert_eq!(level.house_idle_ticks(), seconds(4));
+    }
+
+    #[test]
+    fn each_level_reads_its_own_row() {
+        let mut level = Level::default();
+        for row in LEVEL_TABLE.iter() {
+            assert!(ptr::eq(level.spec(), row), "level {}", level.number());
+            level = level.next();
+        }
+    }
+
+    #[test]
+    fn levels_past_the_table_play_like_its_last_row() {
+        let last = LEVEL_TABLE.last().unwrap();
+        for &number in &[LEVEL_TABLE.len() as u32 + 1, 100, 255] {
+            assert!(ptr::eq(level(number).spec(), last), "level {}", number);
+        }
+    }
+
+    #[test]
+    fn fruit_history_starts_with_a_single_cherry() {
+        let fruit: Vec<Fruit> = level(1).fruit_history(7).collect();
+        assert_eq!(fruit, [Fruit::Cherries]);
+    }
+
+    #[test]
+    fn fruit_history_runs_newest_first() {
+        let fruit: Vec<Fruit> = level(8).fruit_history(7).collect();
+        assert_eq!(fruit, [
+            Fruit::Melon, Fruit::Melon, Fruit::Apple, Fruit::Apple,
+            Fruit::Peach, Fruit::Peach, Fruit::Strawberry,
+        ]);
+    }
+
+    #[test]
+    fn fruit_history_past_the_table_is_all_keys() {
+        let fruit: Vec<Fruit> = level(30).fruit_history(7).collect();
+        assert_eq!(fruit, [Fruit::Key; 7]);
+    }
+
+    #[test]
+    fn elroy_stages_start_at_their_thresholds() {
+        for number in 1..=LEVEL_TABLE.len() as u32 {
+            let level = level(number);
+            let [first, second] = &level.spec().elroy;
+            let (first_dots, second_dots) = (first.dots_left as usize, second.dots_left as usize);
+            assert!(level.elroy(first_dots + 1).is_none(), "level {}", number);
+            assert!(ptr::eq(level.elroy(first_dots).unwrap(), first), "level {}", number);
+            assert!(ptr::eq(level.elroy(second_dots + 1).unwrap(), first), "level {}", number);
+            assert!(ptr::eq(level.elroy(second_dots).unwrap(), second), "level {}", number);
+            assert!(ptr::eq(level.elroy(0).unwrap(), second), "level {}", number);
+        }
+    }
+
+    #[test]
+    fn ghosts_stop_turning_blue_on_later_levels() {
+        assert_eq!(level(17).fright_time().ticks, 0);
+        assert!(level(18).fright_time().ticks > 0);
+        for number in 19..=30 {
+            assert_eq!(level(number).fright_time().ticks, 0, "level {}", number);
+        }
+    }
+}
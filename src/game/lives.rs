@@ -0,0 +1,81 @@
+// Lives at the start of a game, counting the one being played.
+const STARTING_LIVES: u32 = 3;
+
+// Score that earns the one extra life.
+const EXTRA_LIFE_POINTS: u32 = 10_000;
+
+pub(crate) struct Lives {
+    remaining: u32,
+    extra_awarded: bool,
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        Self {
+            remaining: STARTING_LIVES,
+            extra_awarded: false,
+        }
+    }
+}
+
+impl Lives {
+    /// Lives left, counting the one being played.
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    /// Lives waiting in reserve, as shown at the bottom of the screen.
+    pub fn spare(&self) -> u32 {
+        self.remaining.saturating_sub(1)
+    }
+
+    pub fn lose(&mut self) {
+        self.remaining = self.remaining.saturating_sub(1);
+    }
+
+    /// Award the extra life if `points` has just crossed the threshold. Returns true if it
+    /// was awarded.
+    pub fn check_extra(&mut self, points: u32) -> bool {
+        if self.extra_awarded || points < EXTRA_LIFE_POINTS {
+            return false;
+        }
+        self.extra_awarded = true;
+        self.remaining += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_life_at_ten_thousand() {
+        let mut lives = Lives::default();
+        assert!(!lives.check_extra(EXTRA_LIFE_POINTS - 10));
+        assert_eq!(lives.remaining(), STARTING_LIVES);
+        assert!(lives.check_extra(EXTRA_LIFE_POINTS));
+        assert_eq!(lives.remaining(), STARTING_LIVES + 1);
+    }
+
+    #[test]
+    fn extra_life_is_only_awarded_once() {
+        let mut lives = Lives::default();
+        assert!(lives.check_extra(EXTRA_LIFE_POINTS + 500));
+        assert!(!lives.check_extra(EXTRA_LIFE_POINTS + 510));
+        assert!(!lives.check_extra(2 * EXTRA_LIFE_POINTS));
+        assert_eq!(lives.remaining(), STARTING_LIVES + 1);
+    }
+
+    #[test]
+    fn lives_run_out_at_zero() {
+        let mut lives = Lives::default();
+        for left in (0..STARTING_LIVES).rev() {
+            lives.lose();
+            assert_eq!(lives.remaining(), left);
+        }
+        assert_eq!(lives.spare(), 0);
+        lives.lose();
+        assert_eq!(lives.remaining(), 0);
+    }
+}
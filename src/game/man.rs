@@ -0,0 +1,72 @@
+use super::consts::Direction;
+use super::events::DotEaten;
+use super::map::{Map, DotKind, TileInformation};
+use super::sprite::Sprite;
+
+// Movement ticks PacMan loses for each thing he eats.
+const DOT_PAUSE_TICKS: u32 = 1;
+const ENERGIZER_PAUSE_TICKS: u32 = 3;
+
+// Where PacMan starts each life: just below the ghost house, facing right.
+const START: (u32, u32, u32, u32) = (14, 9, 0, 4);
+
+pub(crate) struct PacMan {
+    sprite: Sprite,
+}
+
+impl PacMan {
+    pub fn new() -> Self {
+        let start = TileInformation::from(START);
+        Self {
+            sprite: Sprite::new(start, Direction::Right, vec![0, 1, 2]),
+        }
+    }
+
+    pub fn sprite(&self) -> &Sprite {
+        &self.sprite
+    }
+
+    /// Stand PacMan somewhere in particular.
+    #[cfg(test)]
+    pub fn place(&mut self, sprite: Sprite) {
+        self.sprite = sprite;
+    }
+
+    /// Turn toward the joystick direction if the maze allows it.
+    pub fn steer(&mut self, map: &Map, direction: Direction, speed: u32) {
+        let indexes = match direction {
+            Direction::Up => vec![28, 29, 2],
+            Direction::Left => vec![14, 15, 2],
+            Direction::Down => vec![42, 43, 2],
+            Direction::Right => vec![0, 1, 2],
+        };
+        let (x, y) = self.sprite.tile_info().tile();
+        if let Some((_, new_tile)) = map.get_adjacent(x, y, direction) {
+            if new_tile.is_valid_path() {
+                // Cornering is handled by the sprite as it moves.
+                // this is pacman-specific. The ghosts do not corner.
+                self.sprite.update(speed, direction, indexes);
+            }
+        }
+    }
+
+    pub fn tick(&mut self, map: &Map, speed: u32) {
+        self.sprite.set_speed(speed);
+        self.sprite.tick(map);
+    }
+
+    pub fn hold(&mut self) {
+        self.sprite.hold();
+    }
+
+    /// Clear whatever PacMan is standing on. He loses a little ground for every mouthful.
+    pub fn eat(&mut self, map: &mut Map) -> Option<DotEaten> {
+        let (x, y) = self.sprite.tile_info().tile();
+        let kind = map.remove_dot(x, y)?;
+        self.sprite.pause(match kind {
+            DotKind::Dot => DOT_PAUSE_TICKS,
+            DotKind::Energizer => ENERGIZER_PAUSE_TICKS,
+        });
+        Some(DotEaten { tile: (x, y), kind })
+    }
+}
@@ -0,0 +1,523 @@
+use smallvec::SmallVec;
+use ndarray::{Array, Array2};
+
+use super::consts::{TILE_SIZE, Direction, TILES_WIDE, TILES_HIGH};
+use super::ghost::Personality;
+
+const I: bool = false;
+const X: bool = true;
+
+const N: Option<DotKind> = None;
+const D: Option<DotKind> = Some(DotKind::Dot);
+const E: Option<DotKind> = Some(DotKind::Energizer);
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) struct TileInformation(u32, u32, u32, u32);
+
+impl TileInformation {
+    pub fn new() -> Self {
+        Self(0, 0, 0, 0)
+    }
+
+    pub fn x(&self) -> u32 {
+        self.0
+    }
+
+    pub fn y(&self) -> u32 {
+        self.1
+    }
+
+    pub fn x_offset(&self) -> u32 {
+        self.2
+    }
+
+    pub fn y_offset(&self) -> u32 {
+        self.3
+    }
+
+    pub fn tile(&self) -> (u32, u32) {
+        (self.x(), self.y())
+    }
+
+    /// Sitting exactly on the middle pixel of the tile.
+    pub fn is_centered(&self) -> bool {
+        let center = TILE_SIZE as u32 / 2;
+        self.x_offset() == center && self.y_offset() == center
+    }
+
+    pub fn from_pixels(x: u32, y: u32) -> Self {
+        let tile_size = TILE_SIZE as u32;
+        Self(x / tile_size, y / tile_size, x % tile_size, y % tile_size)
+    }
+
+    /// Absolute position in unscaled pixels from the bottom left of the map.
+    pub fn pixels(&self) -> (u32, u32) {
+        let tile_size = TILE_SIZE as u32;
+        (self.x() * tile_size + self.x_offset(), self.y() * tile_size + self.y_offset())
+    }
+
+    /// The position one pixel over in the given direction, if it's still on the map.
+    pub fn neighbor_pixel(&self, direction: Direction) -> Option<Self> {
+        let (x, y) = self.pixels();
+        let (x, y) = match direction {
+            Direction::Up => (x, y + 1),
+            Direction::Right => (x + 1, y),
+            Direction::Down => (x, y.checked_sub(1)?),
+            Direction::Left => (x.checked_sub(1)?, y),
+        };
+        if x < (TILES_WIDE as u32) * TILE_SIZE as u32 && y < (TILES_HIGH as u32) * TILE_SIZE as u32 {
+            Some(Self::from_pixels(x, y))
+        } else {
+            None
+        }
+    }
+
+    pub fn past_center(&self, facing: Direction) -> bool {
+        let center = TILE_SIZE as u32 / 2;
+        match facing {
+            Direction::Up => {
+                self.y_offset() > center
+            },
+            Direction::Right => {
+                self.x_offset() > center
+            },
+            Direction::Down => {
+                self.y_offset() < center
+            },
+            Direction::Left => {
+                self.x_offset() < center
+            },
+        }
+    }
+
+    pub fn edge(&self) -> Option<Direction> {
+        if self.x() == (TILES_WIDE - 1) as u32 {
+            Some(Direction::Right)
+        } else if self.x() == 0 {
+            Some(Direction::Left)
+        } else if self.y() == (TILES_HIGH - 1) as u32 {
+            Some(Direction::Up)
+        } else if self.y() == 0 {
+            Some(Direction::Down)
+        } else {
+            None
+        }
+    }
+
+    pub fn align(&self, facing: Direction) -> Option<Direction> {
+        let center = TILE_SIZE as u32 / 2;
+        match facing {
+            Direction::Up | Direction::Down => {
+                if self.x_offset() == center {
+                    None
+                } else if self.x_offset() > center {
+                    Some(Direction::Left)
+                } else {
+                    Some(Direction::Right)
+                }
+            },
+            Direction::Left | Direction::Right => {
+                if self.y_offset() == center {
+                    None
+                } else if self.y_offset() > center {
+                    Some(Direction::Down)
+                } else {
+                    Some(Direction::Up)
+                }
+            },
+        }
+    }
+}
+
+impl From<(u32, u32, u32, u32)> for TileInformation {
+    fn from(inp: (u32, u32, u32, u32)) -> Self {
+        Self(inp.0, inp.1, inp.2, inp.3)
+    }
+}
+
+impl Default for TileInformation {
+    fn default() -> Self {
+        Self(0, 0, 0, 0)
+    }
+}
+
+static MAP_PATH_VALIDITY: &[bool] = &[
+    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
+    I, I, I, X, X, X, X, X, X, X, X, X, X, X, X, I, I, X, X, X, X, X, X, X, X, X, X, X, X, I, I, I,
+    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
+    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
+    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
+    I, I, I, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, I, I, I,
+    I, I, I, X, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, X, I, I, I,
+    I, I, I, X, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, X, I, I, I,
+    I, I, I, X, X, X, X, X, X, I, I, X, X, X, X, I, I, X, X, X, X, I, I, X, X, X, X, X, X, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, X, X, X, X, X, X, X, X, X, X, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
+    X, X, X, X, X, X, X, X, X, X, X, X, I, I, I, I, I, I, I, I, X, X, X, X, X, X, X, X, X, X, X, X,
+    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, X, X, X, X, X, X, X, X, X, X, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
+    I, I, I, X, X, X, X, X, X, X, X, X, X, X, X, I, I, X, X, X, X, X, X, X, X, X, X, X, X, I, I, I,
+    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
+    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
+    I, I, I, X, X, X, I, I, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, I, I, X, X, X, I, I, I,
+    I, I, I, I, I, X, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, X, I, I, I, I, I,
+    I, I, I, I, I, X, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, X, I, I, I, I, I,
+    I, I, I, X, X, X, X, X, X, I, I, X, X, X, X, I, I, X, X, X, X, I, I, X, X, X, X, X, X, I, I, I,
+    I, I, I, X, I, I, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, I, I, X, I, I, I,
+    I, I, I, X, I, I, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, I, I, X, I, I, I,
+    I, I, I, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, I, I, I,
+    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
+    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
+];
+
+// The door on top of the ghost house. Only ghosts can use it.
+static GHOST_DOOR_TILES: &[(u32, u32)] = &[(15, 20), (16, 20)];
+
+// The four tiles, above the ghost house and above PacMan's start, where ghosts that are
+// chasing or scattering can't turn up.
+pub(crate) static NO_UP_TILES: &[(u32, u32)] = &[(14, 21), (17, 21), (14, 9), (17, 9)];
+
+// Ghosts crawl through the tunnels at a bit over half their usual speed: 40% of full speed
+// against 75% on the first level, and about the same ratio on every level after.
+const TUNNEL_SPEED_PERCENT: u32 = 53;
+
+// What each path tile holds at the start of a level. Same orientation as MAP_PATH_VALIDITY.
+static MAP_DOT_LAYOUT: &[Option<DotKind>] = &[
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N,
+    N, N, N, D, D, D, D, D, D, D, D, D, D, D, D, N, N, D, D, D, D, D, D, D, D, D, D, D, D, N, N, N,
+    N, N, N, D, N, N, N, N, D, N, N, N, N, N, D, N, N, D, N, N, N, N, N, D, N, N, N, N, D, N, N, N,
+    N, N, N, E, N, N, N, N, D, N, N, N, N, N, D, N, N, D, N, N, N, N, N, D, N, N, N, N, E, N, N, N,
+    N, N, N, D, N, N, N, N, D, N, N, N, N, N, D, N, N, D, N, N, N, N, N, D, N, N, N, N, D, N, N, N,
+    N, N, N, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, N, N, N,
+    N, N, N, D, N, N, N, N, D, N, N, D, N, N, N, N, N, N, N, N, D, N, N, D, N, N, N, N, D, N, N, N,
+    N, N, N, D, N, N, N, N, D, N, N, D, N, N, N, N, N, N, N, N, D, N, N, D, N, N, N, N, D, N, N, N,
+    N, N, N, D, D, D, D, D, D, N, N, D, D, D, D, N, N, D, D, D, D, N, N, D, D, D, D, D, D, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N, N, N, N, N, N, N, D, N, N, N, N, N, N, N, N,
+    N, N, N, D, D, D, D, D, D, D, D, D, D, D, D, N, N, D, D, D, D, D, D, D, D, D, D, D, D, N, N, N,
+    N, N, N, D, N, N, N, N, D, N, N, N, N, N, D, N, N, D, N, N, N, N, N, D, N, N, N, N, D, N, N, N,
+    N, N, N, D, N, N, N, N, D, N, N, N, N, N, D, N, N, D, N, N, N, N, N, D, N, N, N, N, D, N, N, N,
+    N, N, N, E, D, D, N, N, D, D, D, D, D, D, D, N, N, D, D, D, D, D, D, D, N, N, D, D, E, N, N, N,
+    N, N, N, N, N, D, N, N, D, N, N, D, N, N, N, N, N, N, N, N, D, N, N, D, N, N, D, N, N, N, N, N,
+    N, N, N, N, N, D, N, N, D, N, N, D, N, N, N, N, N, N, N, N, D, N, N, D, N, N, D, N, N, N, N, N,
+    N, N, N, D, D, D, D, D, D, N, N, D, D, D, D, N, N, D, D, D, D, N, N, D, D, D, D, D, D, N, N, N,
+    N, N, N, D, N, N, N, N, N, N, N, N, N, N, D, N, N, D, N, N, N, N, N, N, N, N, N, N, D, N, N, N,
+    N, N, N, D, N, N, N, N, N, N, N, N, N, N, D, N, N, D, N, N, N, N, N, N, N, N, N, N, D, N, N, N,
+    N, N, N, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, D, N, N, N,
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N,
+    N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N, N,
+];
+
+pub(crate) struct Map {
+    tiles: Array2<Tile>,
+    dots_total: usize,
+    // Which path tile each actor was last placed on, so it can be taken off again.
+    placements: SmallVec<[(TileActor, (u32, u32)); 8]>,
+}
+
+impl Map {
+    pub fn get_tile(&self, x: u32, y:u32) -> &Tile {
+        self.try_tile(x, y).expect("bad tile coords?")
+    }
+
+    pub fn try_tile(&self, x: u32, y:u32) -> Option<&Tile> {
+        self.tiles.get((x as usize, y as usize))
+    }
+
+    pub fn get_adjacent(&self,
+                        mut x: u32,
+                        mut y: u32,
+                        direction: Direction) -> Option<((u32, u32), &Tile)> {
+        match direction {
+            Direction::Up => {
+                if y == (TILES_HIGH - 1) as u32 {
+                    return None;
+                } else {
+                    y += 1;
+                }
+            },
+            Direction::Right => {
+                if x == (TILES_WIDE - 1) as u32 {
+                    return None;
+                } else {
+                    x += 1;
+                }
+            },
+            Direction::Down => {
+                if y == 0 {
+                    return None;
+                } else {
+                    y -= 1;
+                }
+            },
+            Direction::Left => {
+                if x == 0 {
+                    return None;
+                } else {
+                    x -= 1;
+                }
+            },
+        }
+        Some(((x, y), self.get_tile(x, y)))
+    }
+
+    /// Whether the row runs off both sides of the map, so sprites leaving one side come back
+    /// in on the other.
+    pub fn wraps(&self, y: u32) -> bool {
+        let right = (TILES_WIDE - 1) as u32;
+        self.try_tile(0, y).map_or(false, |tile| tile.is_valid_path())
+            && self.try_tile(right, y).map_or(false, |tile| tile.is_valid_path())
+    }
+
+    // The tunnels are the stretches of a wrapping row between the edge of the map and the
+    // first turn off it.
+    fn mark_tunnels(&mut self) {
+        let right = (TILES_WIDE - 1) as u32;
+        for y in 0..TILES_HIGH as u32 {
+            if self.wraps(y) {
+                self.mark_tunnel(y, 0..=right);
+                self.mark_tunnel(y, (0..=right).rev());
+            }
+        }
+    }
+
+    fn mark_tunnel(&mut self, y: u32, inward: impl Iterator<Item = u32>) {
+        for x in inward {
+            let turns = [Direction::Up, Direction::Down].iter().any(|&direction| {
+                self.get_adjacent(x, y, direction)
+                    .map_or(false, |(_, tile)| tile.is_valid_path())
+            });
+            match self.tiles.get_mut((x as usize, y as usize)) {
+                Some(Tile::Path(path)) if !turns => {
+                    path.tunnel_speed_percent = Some(TUNNEL_SPEED_PERCENT);
+                },
+                _ => break,
+            }
+        }
+    }
+
+    pub fn dot(&self, x: u32, y: u32) -> Option<DotKind> {
+        self.tiles
+            .get((x as usize, y as usize))
+            .and_then(|tile| tile.dot())
+    }
+
+    /// Clears the dot or energizer from a tile, returning what was there.
+    pub fn remove_dot(&mut self, x: u32, y: u32) -> Option<DotKind> {
+        match self.tiles.get_mut((x as usize, y as usize)) {
+            Some(Tile::Path(path)) => path.dot.take(),
+            _ => None,
+        }
+    }
+
+    /// Record that `actor` now occupies `tile`, or has left the paths (e.g. for the ghost
+    /// house) if `None`.
+    pub fn place_actor(&mut self, actor: TileActor, tile: Option<(u32, u32)>) {
+        if let Some(tile) = tile {
+            if self.placements.contains(&(actor, tile)) {
+                return;
+            }
+        }
+        if let Some(index) = self.placements.iter().position(|&(placed, _)| placed == actor) {
+            let (_, (x, y)) = self.placements.swap_remove(index);
+            if let Some(Tile::Path(path)) = self.tiles.get_mut((x as usize, y as usize)) {
+                path.contents.retain(|occupant| *occupant != actor);
+            }
+        }
+        if let Some((x, y)) = tile {
+            if let Some(Tile::Path(path)) = self.tiles.get_mut((x as usize, y as usize)) {
+                path.contents.push(actor);
+                self.placements.push((actor, (x, y)));
+            }
+        }
+    }
+
+    /// Everyone standing on a tile.
+    pub fn actors_at(&self, x: u32, y: u32) -> &[TileActor] {
+        match self.tiles.get((x as usize, y as usize)) {
+            Some(Tile::Path(path)) => &path.contents,
+            _ => &[],
+        }
+    }
+
+    /// Dots and energizers still on the board.
+    pub fn dots_remaining(&self) -> usize {
+        self.tiles.iter().filter(|tile| tile.dot().is_some()).count()
+    }
+
+    /// Dots and energizers the level started with.
+    pub fn dots_total(&self) -> usize {
+        self.dots_total
+    }
+
+    pub fn dots_eaten(&self) -> usize {
+        self.dots_total - self.dots_remaining()
+    }
+}
+
+impl Default for Map {
+    fn default() -> Self {
+        assert_eq!(MAP_PATH_VALIDITY.len(), TILES_WIDE * TILES_HIGH);
+        assert_eq!(MAP_DOT_LAYOUT.len(), TILES_WIDE * TILES_HIGH);
+        let tiles = Array::from_shape_fn((TILES_WIDE, TILES_HIGH),
+                                         |(i, j)|  {
+                                             let idx = (((TILES_HIGH - 1) - j) * TILES_WIDE)+i;
+                                             if GHOST_DOOR_TILES.contains(&(i as u32, j as u32)) {
+                                                 Tile::GhostDoor
+                                             } else if MAP_PATH_VALIDITY[idx] {
+                                                 Tile::Path(PathTile {
+                                                     dot: MAP_DOT_LAYOUT[idx],
+                                                     restricted: restricted_directions(i as u32, j as u32),
+                                                     ..Default::default()
+                                                 })
+                                             } else {
+                                                 assert!(MAP_DOT_LAYOUT[idx].is_none(), "dot placed on a wall");
+                                                 Tile::Invalid
+                                             }
+                                         });
+        let dots_total = tiles.iter().filter(|tile| tile.dot().is_some()).count();
+        let mut map = Self {
+            tiles,
+            dots_total,
+            placements: SmallVec::new(),
+        };
+        map.mark_tunnels();
+        map
+    }
+}
+
+fn restricted_directions(x: u32, y: u32) -> &'static [Direction] {
+    if NO_UP_TILES.contains(&(x, y)) {
+        &[Direction::Up]
+    } else {
+        &[]
+    }
+}
+
+#[derive(Clone)]
+pub(crate) enum Tile {
+    Invalid,
+    Path(PathTile),
+    GhostDoor,
+}
+
+impl Tile {
+    pub fn is_valid_path(&self) -> bool {
+        match self {
+            &Tile::Path(_) => true,
+            _ => false,
+        }
+    }
+
+    pub fn is_ghost_door(&self) -> bool {
+        match self {
+            &Tile::GhostDoor => true,
+            _ => false,
+        }
+    }
+
+    pub fn dot(&self) -> Option<DotKind> {
+        match self {
+            &Tile::Path(ref path) => path.dot,
+            _ => None,
+        }
+    }
+
+    /// Directions ghosts may not turn to from this tile, unless frightened or going home.
+    pub fn restricted_directions(&self) -> &'static [Direction] {
+        match self {
+            &Tile::Path(ref path) => path.restricted,
+            _ => &[],
+        }
+    }
+
+    /// For tunnel tiles, the percentage of their usual speed ghosts slow down to.
+    pub fn tunnel_speed_percent(&self) -> Option<u32> {
+        match self {
+            &Tile::Path(ref path) => path.tunnel_speed_percent,
+            _ => None,
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum DotKind {
+    Dot,
+    Energizer,
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TileActor {
+    PacMan,
+    Ghost(Personality),
+    Bonus,
+}
+
+#[derive(Default, Clone)]
+pub(crate) struct PathTile {
+    contents: SmallVec<[TileActor; 8]>,
+    dot: Option<DotKind>,
+    restricted: &'static [Direction],
+    tunnel_speed_percent: Option<u32>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TUNNEL_ROW: u32 = 18;
+
+    #[test]
+    fn only_the_tunnel_row_wraps() {
+        let map = Map::default();
+        for y in 0..TILES_HIGH as u32 {
+            assert_eq!(map.wraps(y), y == TUNNEL_ROW, "row {}", y);
+        }
+    }
+
+    #[test]
+    fn tunnels_run_from_the_edges_to_the_first_turn() {
+        let map = Map::default();
+        for x in 0..TILES_WIDE as u32 {
+            let expected = if x <= 7 || x >= 24 { Some(TUNNEL_SPEED_PERCENT) } else { None };
+            let tunnel = map.get_tile(x, TUNNEL_ROW).tunnel_speed_percent();
+            assert_eq!(tunnel, expected, "column {}", x);
+        }
+        for y in (0..TILES_HIGH as u32).filter(|&y| y != TUNNEL_ROW) {
+            for x in 0..TILES_WIDE as u32 {
+                assert_eq!(map.get_tile(x, y).tunnel_speed_percent(), None, "({}, {})", x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn no_up_tiles_are_marked() {
+        let map = Map::default();
+        for &(x, y) in NO_UP_TILES {
+            assert_eq!(map.get_tile(x, y).restricted_directions(), [Direction::Up]);
+        }
+    }
+}
@@ -0,0 +1,879 @@
+//! The game itself, free of any rendering or windowing. Everything advances in fixed
+//! ticks through `Game::tick`; the Bevy plugins only draw the result and feed in input,
+//! so the same code can run headless.
+
+pub(crate) mod autopilot;
+pub(crate) mod bonus;
+pub(crate) mod consts;
+pub(crate) mod cutscene;
+pub(crate) mod events;
+pub(crate) mod ghost;
+pub(crate) mod house;
+pub(crate) mod level;
+pub(crate) mod lives;
+pub(crate) mod man;
+pub(crate) mod map;
+pub(crate) mod mode;
+pub(crate) mod quirks;
+pub(crate) mod rng;
+pub(crate) mod score;
+pub(crate) mod sprite;
+pub(crate) mod targeting;
+
+use std::vec::Drain;
+
+use smallvec::SmallVec;
+
+use bonus::{BONUS_DOTS, Bonus};
+use consts::Direction;
+use cutscene::{Cutscene, intermission_after};
+use events::{
+    BonusEaten, BonusExpired, BonusShown, Event, ExtraLife, FrightOver, Frightened, GameOver,
+    GhostEaten, IntermissionStarted, LevelComplete, LevelStarted, LifeLost, ModeChanged,
+    PacManCaught, PacManDying,
+};
+use ghost::{Ghost, GhostState, Personality};
+use house::GhostHouse;
+use level::{ElroyStage, Level, speed};
+use lives::Lives;
+use man::PacMan;
+use map::{DotKind, Map, TileActor};
+use mode::{FrightTimer, GhostMode, ModeTimer};
+use quirks::Quirks;
+use rng::Prng;
+use score::Score;
+use sprite::Sprite;
+
+pub(crate) const TICKS_PER_SECOND: u32 = 60;
+
+// How long everything stops to show the points for eating a ghost.
+const GHOST_EATEN_FREEZE_TICKS: u32 = TICKS_PER_SECOND;
+
+// PacMan's death: everything stops for a moment, the ghosts vanish and he shrivels up
+// through frames 3-13 of the sheet, then a short blank pause before the next life.
+const DEATH_FREEZE_TICKS: u32 = TICKS_PER_SECOND;
+const DEATH_FIRST_FRAME: u32 = 3;
+const DEATH_FRAMES: u32 = 11;
+const DEATH_FRAME_TICKS: u32 = 8;
+const DEATH_ANIMATION_END: u32 = DEATH_FREEZE_TICKS + DEATH_FRAMES * DEATH_FRAME_TICKS;
+const DEATH_TICKS: u32 = DEATH_ANIMATION_END + TICKS_PER_SECOND;
+
+// Clearing the board: everything stops for a moment, then the ghosts vanish and the maze
+// flashes white a few times before the next level is set up.
+const LEVEL_COMPLETE_PAUSE_TICKS: u32 = TICKS_PER_SECOND;
+const MAZE_FLASHES: u32 = 4;
+const MAZE_FLASH_TICKS: u32 = 12;
+const LEVEL_COMPLETE_TICKS: u32 = LEVEL_COMPLETE_PAUSE_TICKS + MAZE_FLASHES * 2 * MAZE_FLASH_TICKS;
+
+/// Identifies one of the things moving through the maze.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Actor {
+    PacMan,
+    Ghost(Personality),
+}
+
+// The pause after eating a ghost, during which PacMan and that ghost are hidden.
+struct Freeze {
+    ticks_left: u32,
+    ghost: Personality,
+}
+
+pub(crate) struct Game {
+    map: Map,
+    level: Level,
+    score: Score,
+    lives: Lives,
+    pacman: PacMan,
+    ghosts: Vec<Ghost>,
+    house: GhostHouse,
+    // After PacMan loses a life, Blinky doesn't go Elroy again until Clyde is out.
+    elroy_suspended: bool,
+    bonus: Option<Bonus>,
+    mode_timer: ModeTimer,
+    fright: Option<FrightTimer>,
+    ghost_chain: u32,
+    freeze: Option<Freeze>,
+    // Ticks since PacMan was caught, while the death sequence plays.
+    dying: Option<u32>,
+    // Ticks since the last dot was eaten, while the level complete sequence plays.
+    clearing: Option<u32>,
+    // The scene played between some levels, once the maze has finished flashing.
+    intermission: Option<Cutscene>,
+    game_over: bool,
+    rng: Prng,
+    // Kept apart from `rng` so the fruit timer doesn't disturb the ghosts' patterns.
+    bonus_rng: Prng,
+    quirks: Quirks,
+    ticks: u64,
+    events: Vec<Event>,
+}
+
+impl Game {
+    pub fn new() -> Self {
+        Self::with_quirks(Quirks::default())
+    }
+
+    pub fn with_quirks(quirks: Quirks) -> Self {
+        let level = Level::default();
+        Self {
+            map: Map::default(),
+            score: Score::default(),
+            lives: Lives::default(),
+            pacman: PacMan::new(),
+            ghosts: spawn_ghosts(&quirks),
+            house: GhostHouse::default(),
+            elroy_suspended: false,
+            bonus: None,
+            mode_timer: ModeTimer::new(&level),
+            fright: None,
+            ghost_chain: 0,
+            freeze: None,
+            dying: None,
+            clearing: None,
+            intermission: None,
+            game_over: false,
+            rng: Prng::default(),
+            bonus_rng: Prng::default(),
+            quirks,
+            level,
+            ticks: 0,
+            events: Vec::new(),
+        }
+    }
+
+    pub fn map(&self) -> &Map {
+        &self.map
+    }
+
+    pub fn level(&self) -> &Level {
+        &self.level
+    }
+
+    pub fn score(&self) -> &Score {
+        &self.score
+    }
+
+    pub fn lives(&self) -> &Lives {
+        &self.lives
+    }
+
+    pub fn pacman(&self) -> &PacMan {
+        &self.pacman
+    }
+
+    pub fn ghosts(&self) -> &[Ghost] {
+        &self.ghosts
+    }
+
+    pub fn ghost(&self, personality: Personality) -> &Ghost {
+        self.ghosts
+            .iter()
+            .find(|ghost| ghost.personality() == personality)
+            .expect("ghost not in play")
+    }
+
+    /// The fruit below the ghost house, while it's out.
+    pub fn bonus(&self) -> Option<&Bonus> {
+        self.bonus.as_ref()
+    }
+
+    /// Blinky's Cruise Elroy stage, if he's sped up. He also ignores scatter while it lasts.
+    pub fn elroy(&self) -> Option<&'static ElroyStage> {
+        if self.elroy_suspended {
+            None
+        } else {
+            self.level.elroy(self.map.dots_remaining())
+        }
+    }
+
+    /// The intermission being played, if the game is between levels.
+    pub fn intermission(&self) -> Option<&Cutscene> {
+        self.intermission.as_ref()
+    }
+
+    /// The scatter/chase schedule and where we are in it.
+    pub fn mode_timer(&self) -> &ModeTimer {
+        &self.mode_timer
+    }
+
+    /// The energizer countdown, while one is in effect.
+    pub fn fright_timer(&self) -> Option<&FrightTimer> {
+        self.fright.as_ref()
+    }
+
+    pub fn sprite(&self, actor: Actor) -> &Sprite {
+        match actor {
+            Actor::PacMan => self.pacman.sprite(),
+            Actor::Ghost(personality) => self.ghost(personality).sprite(),
+        }
+    }
+
+    /// Whether an actor should be drawn. PacMan and the ghost he just ate disappear while
+    /// the points are shown, and nobody is on the board during an intermission or once the
+    /// game is over.
+    pub fn is_visible(&self, actor: Actor) -> bool {
+        if self.game_over || self.intermission.is_some() {
+            return false;
+        }
+        if let Some(clearing) = self.clearing {
+            return match actor {
+                Actor::PacMan => true,
+                Actor::Ghost(_) => clearing < LEVEL_COMPLETE_PAUSE_TICKS,
+            };
+        }
+        if let Some(dying) = self.dying {
+            return match actor {
+                Actor::PacMan => dying < DEATH_ANIMATION_END,
+                Actor::Ghost(_) => dying < DEATH_FREEZE_TICKS,
+            };
+        }
+        match (&self.freeze, actor) {
+            (Some(_), Actor::PacMan) => false,
+            (Some(freeze), Actor::Ghost(personality)) => freeze.ghost != personality,
+            (None, _) => true,
+        }
+    }
+
+    /// Whether the maze should be drawn white, as it flashes at the end of a level.
+    pub fn is_maze_white(&self) -> bool {
+        match self.clearing {
+            Some(clearing) if clearing >= LEVEL_COMPLETE_PAUSE_TICKS => {
+                ((clearing - LEVEL_COMPLETE_PAUSE_TICKS) / MAZE_FLASH_TICKS) % 2 == 0
+            },
+            _ => false,
+        }
+    }
+
+    /// The sprite sheet frame to draw an actor with.
+    pub fn texture_index(&self, actor: Actor) -> u32 {
+        match (self.dying, actor) {
+            (Some(dying), Actor::PacMan) if dying >= DEATH_FREEZE_TICKS => {
+                let frame = (dying - DEATH_FREEZE_TICKS) / DEATH_FRAME_TICKS;
+                DEATH_FIRST_FRAME + frame.min(DEATH_FRAMES - 1)
+            },
+            _ => self.sprite(actor).texture_index(),
+        }
+    }
+
+    /// Everything is stopped while the points for eating a ghost are shown.
+    pub fn is_frozen(&self) -> bool {
+        self.freeze.is_some()
+    }
+
+    /// Ticks run since the game started.
+    pub fn ticks(&self) -> u64 {
+        self.ticks
+    }
+
+    /// PacMan is out of lives.
+    pub fn is_game_over(&self) -> bool {
+        self.game_over
+    }
+
+    /// Advance the game by one tick, with the joystick held in `input`.
+    pub fn tick(&mut self, input: Option<Direction>) {
+        if self.game_over {
+            return;
+        }
+        self.ticks += 1;
+        // Anyone who moves this tick moves on from here; anyone who doesn't is held still.
+        self.pacman.hold();
+        for ghost in self.ghosts.iter_mut() {
+            ghost.hold();
+        }
+        if let Some(intermission) = self.intermission.as_mut() {
+            if intermission.tick() {
+                self.intermission = None;
+                self.start_next_level();
+            }
+            return;
+        }
+        if self.clearing.is_some() {
+            self.tick_clearing();
+            return;
+        }
+        if self.dying.is_some() {
+            self.tick_dying();
+            return;
+        }
+        if self.freeze.is_some() {
+            self.tick_frozen();
+            return;
+        }
+        let speeds = self.level.speeds();
+        let pacman_speed = speed(if self.fright.is_some() {
+            speeds.pacman_frightened
+        } else {
+            speeds.pacman
+        });
+        if let Some(direction) = input {
+            self.pacman.steer(&self.map, direction, pacman_speed);
+        }
+        self.pacman.tick(&self.map, pacman_speed);
+        let dot_eaten = self.pacman.eat(&mut self.map);
+        if let Some(dot_eaten) = dot_eaten {
+            self.score.add_dot(dot_eaten.kind);
+            self.events.push(Event::DotEaten(dot_eaten));
+            if dot_eaten.kind == DotKind::Energizer {
+                self.frighten();
+            }
+            if self.map.dots_remaining() == 0 {
+                let level = self.level.number();
+                self.clearing = Some(0);
+                self.bonus = None;
+                self.events.push(Event::LevelComplete(LevelComplete { level }));
+                self.check_extra_life();
+                return;
+            }
+            if BONUS_DOTS.contains(&self.map.dots_eaten()) {
+                let fruit = self.level.fruit();
+                self.bonus = Some(Bonus::new(fruit, &mut self.bonus_rng));
+                self.events.push(Event::BonusShown(BonusShown { fruit }));
+            }
+        }
+        if let Some(bonus) = self.bonus.as_mut() {
+            if bonus.tick() {
+                self.bonus = None;
+                self.events.push(Event::BonusExpired(BonusExpired));
+            }
+        }
+        self.house.tick(&mut self.ghosts, &self.level, dot_eaten.is_some());
+        if self.elroy_suspended && !self.ghost(Personality::Clyde).is_home() {
+            self.elroy_suspended = false;
+        }
+
+        // The scatter/chase clock stops while the ghosts are blue.
+        if let Some(fright) = self.fright.as_mut() {
+            if fright.tick() {
+                self.fright = None;
+                for ghost in self.ghosts.iter_mut() {
+                    ghost.calm();
+                }
+                self.events.push(Event::FrightOver(FrightOver));
+            } else {
+                let white = fright.is_white();
+                for ghost in self.ghosts.iter_mut() {
+                    ghost.flash(white);
+                }
+            }
+        } else if let Some(mode) = self.mode_timer.tick() {
+            for ghost in self.ghosts.iter_mut() {
+                ghost.reverse();
+            }
+            self.events.push(Event::ModeChanged(ModeChanged { mode }));
+        }
+
+        let mode = self.mode_timer.mode();
+        let elroy = self.elroy();
+        // Everyone picks a target before anyone moves, since Inky watches Blinky.
+        let blinky = self.ghost(Personality::Blinky).sprite();
+        let targets: Vec<_> = self.ghosts
+            .iter()
+            .map(|ghost| if ghost.is_frightened() {
+                None
+            } else if ghost.personality() == Personality::Blinky && elroy.is_some() {
+                Some(ghost.target(GhostMode::Chase, self.pacman.sprite(), blinky))
+            } else {
+                Some(ghost.target(mode, self.pacman.sprite(), blinky))
+            })
+            .collect();
+        for (ghost, target) in self.ghosts.iter_mut().zip(targets) {
+            let (x, y) = ghost.sprite().tile_info().tile();
+            let tunnel = self.map.get_tile(x, y).tunnel_speed_percent();
+            // The tunnel slows every ghost down, blue or Elroy; only eyes rush through.
+            let ghost_speed = speed(match (tunnel, elroy) {
+                (Some(percent), _) => speeds.ghost * percent / 100,
+                _ if ghost.is_frightened() => speeds.ghost_frightened,
+                (None, Some(stage)) if ghost.personality() == Personality::Blinky => stage.speed,
+                _ => speeds.ghost,
+            });
+            ghost.tick(&self.map, target, ghost_speed, &mut self.rng);
+        }
+
+        self.update_occupancy();
+        self.collide();
+        self.check_extra_life();
+    }
+
+    fn tick_clearing(&mut self) {
+        let clearing = self.clearing.expect("not clearing") + 1;
+        if clearing < LEVEL_COMPLETE_TICKS {
+            self.clearing = Some(clearing);
+            return;
+        }
+        self.clearing = None;
+        if let Some((number, script)) = intermission_after(self.level.number()) {
+            self.intermission = Some(Cutscene::new(script));
+            self.events.push(Event::IntermissionStarted(IntermissionStarted { number }));
+        } else {
+            self.start_next_level();
+        }
+    }
+
+    fn start_next_level(&mut self) {
+        self.level = self.level.next();
+        self.map = Map::default();
+        self.house = GhostHouse::default();
+        self.elroy_suspended = false;
+        self.reset_actors();
+        self.events.push(Event::LevelStarted(LevelStarted { level: self.level.number() }));
+    }
+
+    fn tick_dying(&mut self) {
+        let dying = self.dying.expect("not dying") + 1;
+        self.dying = Some(dying);
+        if dying == DEATH_FREEZE_TICKS {
+            self.events.push(Event::PacManDying(PacManDying));
+        }
+        if dying < DEATH_TICKS {
+            return;
+        }
+        self.dying = None;
+        self.lives.lose();
+        self.events.push(Event::LifeLost(LifeLost { lives: self.lives.remaining() }));
+        if self.lives.remaining() == 0 {
+            self.game_over = true;
+            self.events.push(Event::GameOver(GameOver));
+        } else {
+            self.house.use_global_counter();
+            self.elroy_suspended = true;
+            self.reset_actors();
+        }
+    }
+
+    // Everyone back to their starting places, for a new life or a new level.
+    fn reset_actors(&mut self) {
+        self.pacman = PacMan::new();
+        self.ghosts = spawn_ghosts(&self.quirks);
+        self.mode_timer = ModeTimer::new(&self.level);
+        self.fright = None;
+        self.freeze = None;
+        self.bonus = None;
+        self.rng = Prng::default();
+        self.update_occupancy();
+    }
+
+    fn check_extra_life(&mut self) {
+        if self.lives.check_extra(self.score.points()) {
+            self.events.push(Event::ExtraLife(ExtraLife));
+        }
+    }
+
+    // Only eyes already on their way home keep moving while the game is frozen.
+    fn tick_frozen(&mut self) {
+        let freeze = self.freeze.as_mut().expect("not frozen");
+        let eaten = freeze.ghost;
+        freeze.ticks_left -= 1;
+        if freeze.ticks_left == 0 {
+            self.freeze = None;
+        }
+        for ghost in self.ghosts.iter_mut() {
+            if ghost.is_eyes() && ghost.personality() != eaten {
+                ghost.tick(&self.map, None, 0, &mut self.rng);
+            }
+        }
+        self.update_occupancy();
+    }
+
+    // Keep the map's record of who's standing where up to date.
+    fn update_occupancy(&mut self) {
+        let tile = self.pacman.sprite().tile_info().tile();
+        self.map.place_actor(TileActor::PacMan, Some(tile));
+        for ghost in self.ghosts.iter() {
+            let tile = ghost.sprite().tile_info().tile();
+            self.map.place_actor(TileActor::Ghost(ghost.personality()), Some(tile));
+        }
+        let tile = self.bonus.as_ref().map(|bonus| bonus.tile_info().tile());
+        self.map.place_actor(TileActor::Bonus, tile);
+    }
+
+    // Ghosts sharing PacMan's tile either catch him or, if they're blue, get eaten. Without
+    // the pass-through quirk, a ghost that swapped tiles with PacMan this tick counts too.
+    // The fruit is eaten just by walking onto it.
+    fn collide(&mut self) {
+        let pacman = self.pacman.sprite();
+        let tile = pacman.tile_info().tile();
+        let previous_tile = pacman.previous_tile_info().tile();
+        if self.map.actors_at(tile.0, tile.1).contains(&TileActor::Bonus) {
+            let fruit = self.bonus.take().expect("bonus placed but not out").fruit();
+            let points = fruit.points();
+            self.score.add(points);
+            self.map.place_actor(TileActor::Bonus, None);
+            self.events.push(Event::BonusEaten(BonusEaten { fruit, tile, points }));
+        }
+        let mut colliding: SmallVec<[Personality; 4]> = self.map
+            .actors_at(tile.0, tile.1)
+            .iter()
+            .filter_map(|actor| match actor {
+                TileActor::Ghost(personality) => Some(*personality),
+                _ => None,
+            })
+            .collect();
+        if !self.quirks.pass_through {
+            for ghost in self.ghosts.iter() {
+                let sprite = ghost.sprite();
+                let swapped = sprite.tile_info().tile() == previous_tile
+                    && sprite.previous_tile_info().tile() == tile;
+                if swapped && !colliding.contains(&ghost.personality()) {
+                    colliding.push(ghost.personality());
+                }
+            }
+        }
+
+        for personality in colliding {
+            let ghost = self.ghost_mut(personality);
+            // Eyes pass PacMan by. A ghost still on its way out of the house doesn't: it
+            // shares his tile only once it's up through the door.
+            if let GhostState::Eaten | GhostState::Entering = ghost.state() {
+                continue;
+            }
+            if ghost.is_frightened() {
+                ghost.eat();
+                let points = self.score.add_ghost(self.ghost_chain);
+                self.ghost_chain += 1;
+                self.freeze = Some(Freeze {
+                    ticks_left: GHOST_EATEN_FREEZE_TICKS,
+                    ghost: personality,
+                });
+                self.events.push(Event::GhostEaten(GhostEaten {
+                    ghost: personality,
+                    tile,
+                    points,
+                }));
+                // One at a time: anyone else here gets eaten once the freeze is over.
+                return;
+            } else {
+                self.dying = Some(0);
+                self.bonus = None;
+                self.events.push(Event::PacManCaught(PacManCaught { ghost: personality, tile }));
+                return;
+            }
+        }
+    }
+
+    fn ghost_mut(&mut self, personality: Personality) -> &mut Ghost {
+        self.ghosts
+            .iter_mut()
+            .find(|ghost| ghost.personality() == personality)
+            .expect("ghost not in play")
+    }
+
+    // An energizer always turns the ghosts around, but on later levels they don't turn blue.
+    fn frighten(&mut self) {
+        let fright_time = self.level.fright_time();
+        if fright_time.ticks == 0 {
+            for ghost in self.ghosts.iter_mut() {
+                ghost.reverse();
+            }
+            return;
+        }
+        self.fright = Some(FrightTimer::new(fright_time));
+        self.ghost_chain = 0;
+        for ghost in self.ghosts.iter_mut() {
+            ghost.frighten();
+        }
+        self.events.push(Event::Frightened(Frightened { ticks: fright_time.ticks }));
+    }
+
+    /// Events raised since the last drain, oldest first.
+    pub fn drain_events(&mut self) -> Drain<'_, Event> {
+        self.events.drain(..)
+    }
+}
+
+fn spawn_ghosts(quirks: &Quirks) -> Vec<Ghost> {
+    Personality::ALL
+        .iter()
+        .map(|&personality| Ghost::new(personality, quirks))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bonus::Fruit;
+    use consts::{TILES_HIGH, TILES_WIDE};
+    use map::TileInformation;
+
+    // Along the bottom corridor, well away from where anyone starts.
+    const CORRIDOR: (u32, u32) = (10, 3);
+
+    fn standing(tile: (u32, u32), facing: Direction) -> Sprite {
+        Sprite::new(TileInformation::from((tile.0, tile.1, 4, 4)), facing, vec![0])
+    }
+
+    // A sprite that has just moved one pixel from `from`.
+    fn moved(from: (u32, u32, u32, u32), facing: Direction) -> Sprite {
+        let mut sprite = Sprite::new(TileInformation::from(from), facing, vec![0]);
+        sprite.set_speed(256);
+        sprite.tick_unchecked(|_| true);
+        sprite
+    }
+
+    // Put PacMan and a ghost where they're wanted and see what comes of it.
+    fn meet(
+        game: &mut Game,
+        pacman: Sprite,
+        personality: Personality,
+        ghost: Sprite,
+        state: GhostState,
+    ) -> Vec<Event> {
+        game.pacman.place(pacman);
+        game.ghost_mut(personality).place(ghost, state);
+        game.update_occupancy();
+        game.collide();
+        game.drain_events().collect()
+    }
+
+    // Clear dots, leaving the one on CORRIDOR, until `count - 1` are gone; then have PacMan
+    // eat that one.
+    fn eat_dots(game: &mut Game, count: usize) -> Vec<Event> {
+        for x in 0..TILES_WIDE as u32 {
+            for y in 0..TILES_HIGH as u32 {
+                if game.map.dots_eaten() + 1 == count {
+                    break;
+                }
+                if (x, y) != CORRIDOR && game.map.dot(x, y) == Some(DotKind::Dot) {
+                    game.map.remove_dot(x, y);
+                }
+            }
+        }
+        game.drain_events().for_each(drop);
+        game.pacman.place(standing(CORRIDOR, Direction::Right));
+        game.tick(None);
+        assert_eq!(game.map.dots_eaten(), count);
+        game.drain_events().collect()
+    }
+
+    fn caught(events: &[Event]) -> bool {
+        events.iter().any(|event| match event {
+            Event::PacManCaught(_) => true,
+            _ => false,
+        })
+    }
+
+    fn eaten(events: &[Event]) -> bool {
+        events.iter().any(|event| match event {
+            Event::GhostEaten(_) => true,
+            _ => false,
+        })
+    }
+
+    #[test]
+    fn ghost_on_the_same_tile_catches_pacman() {
+        let mut game = Game::new();
+        let events = meet(
+            &mut game, standing(CORRIDOR, Direction::Right),
+            Personality::Blinky, standing(CORRIDOR, Direction::Left), GhostState::Roaming);
+        assert!(caught(&events));
+    }
+
+    #[test]
+    fn frightened_ghost_on_the_same_tile_is_eaten() {
+        let mut game = Game::new();
+        game.ghost_mut(Personality::Blinky).frighten();
+        let events = meet(
+            &mut game, standing(CORRIDOR, Direction::Right),
+            Personality::Blinky, standing(CORRIDOR, Direction::Left), GhostState::Roaming);
+        assert!(eaten(&events) && !caught(&events));
+        assert_eq!(game.ghost(Personality::Blinky).state(), GhostState::Eaten);
+    }
+
+    #[test]
+    fn eyes_pass_pacman_by() {
+        let mut game = Game::new();
+        let events = meet(
+            &mut game, standing(CORRIDOR, Direction::Right),
+            Personality::Blinky, standing(CORRIDOR, Direction::Left), GhostState::Eaten);
+        assert!(events.is_empty());
+    }
+
+    // PacMan steps right from (10, 3) into (11, 3) as Blinky steps left the other way.
+    fn swap(game: &mut Game) -> Vec<Event> {
+        let (x, y) = CORRIDOR;
+        meet(
+            game, moved((x, y, 7, 4), Direction::Right),
+            Personality::Blinky, moved((x + 1, y, 0, 4), Direction::Left), GhostState::Roaming)
+    }
+
+    #[test]
+    fn swapping_tiles_passes_through_with_the_quirk() {
+        let mut game = Game::new();
+        assert!(swap(&mut game).is_empty());
+    }
+
+    #[test]
+    fn swapping_tiles_is_caught_without_the_quirk() {
+        let mut game = Game::with_quirks(Quirks { pass_through: false, ..Quirks::default() });
+        assert!(caught(&swap(&mut game)));
+    }
+
+    // Pinky just up through the door of the ghost house, still on her way out, meeting
+    // PacMan there. Blinky, who starts on that tile, is moved out of the way first.
+    fn meet_leaving_pinky(game: &mut Game) -> Vec<Event> {
+        game.ghost_mut(Personality::Blinky)
+            .place(standing(CORRIDOR, Direction::Left), GhostState::Roaming);
+        let above_door = Sprite::new(TileInformation::from((16, 21, 0, 4)), Direction::Up, vec![0]);
+        meet(
+            game, standing((16, 21), Direction::Left),
+            Personality::Pinky, above_door, GhostState::Leaving)
+    }
+
+    #[test]
+    fn ghost_leaving_the_house_catches_pacman() {
+        let mut game = Game::new();
+        let events = meet_leaving_pinky(&mut game);
+        assert!(events.iter().any(|event| match event {
+            Event::PacManCaught(caught) => caught.ghost == Personality::Pinky,
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn frightened_ghost_leaving_the_house_drops_back_in() {
+        let mut game = Game::new();
+        game.ghost_mut(Personality::Pinky).frighten();
+        let events = meet_leaving_pinky(&mut game);
+        assert!(eaten(&events) && !caught(&events));
+        assert_eq!(game.ghost(Personality::Pinky).state(), GhostState::Entering);
+    }
+
+    fn points_for_ghost(events: &[Event]) -> Option<u32> {
+        events.iter().find_map(|event| match event {
+            Event::GhostEaten(eaten) => Some(eaten.points),
+            _ => None,
+        })
+    }
+
+    // Eat a blue ghost on the corridor, then wait out the freeze.
+    fn eat_ghost(game: &mut Game, personality: Personality) -> Option<u32> {
+        let events = meet(
+            game, standing(CORRIDOR, Direction::Right),
+            personality, standing(CORRIDOR, Direction::Left), GhostState::Roaming);
+        game.freeze = None;
+        points_for_ghost(&events)
+    }
+
+    #[test]
+    fn each_ghost_on_one_energizer_is_worth_double() {
+        let mut game = Game::new();
+        game.frighten();
+        let points: Vec<_> = Personality::ALL
+            .iter()
+            .map(|&personality| eat_ghost(&mut game, personality))
+            .collect();
+        assert_eq!(points, [Some(200), Some(400), Some(800), Some(1600)]);
+        assert_eq!(game.score().points(), 3000);
+    }
+
+    #[test]
+    fn a_new_energizer_starts_the_chain_over() {
+        let mut game = Game::new();
+        game.frighten();
+        assert_eq!(eat_ghost(&mut game, Personality::Blinky), Some(200));
+        assert_eq!(eat_ghost(&mut game, Personality::Pinky), Some(400));
+        game.frighten();
+        assert_eq!(eat_ghost(&mut game, Personality::Inky), Some(200));
+    }
+
+    #[test]
+    fn eating_a_ghost_freezes_the_game_for_a_second() {
+        let mut game = Game::new();
+        game.frighten();
+        meet(
+            &mut game, standing(CORRIDOR, Direction::Right),
+            Personality::Blinky, standing(CORRIDOR, Direction::Left), GhostState::Roaming);
+        for _ in 0..GHOST_EATEN_FREEZE_TICKS {
+            assert!(game.freeze.is_some());
+            assert!(!game.is_visible(Actor::PacMan));
+            assert!(!game.is_visible(Actor::Ghost(Personality::Blinky)));
+            assert!(game.is_visible(Actor::Ghost(Personality::Pinky)));
+            game.tick(None);
+        }
+        assert!(game.freeze.is_none());
+        assert!(game.is_visible(Actor::PacMan));
+    }
+
+    // Play PacMan's death through to the end.
+    fn die(game: &mut Game) -> Vec<Event> {
+        game.dying = Some(0);
+        while game.dying.is_some() {
+            game.tick(None);
+        }
+        game.drain_events().collect()
+    }
+
+    #[test]
+    fn losing_the_last_life_ends_the_game() {
+        let mut game = Game::new();
+        while game.lives().remaining() > 1 {
+            die(&mut game);
+            assert!(!game.is_game_over());
+        }
+        let events = die(&mut game);
+        assert_eq!(game.lives().remaining(), 0);
+        assert!(game.is_game_over());
+        assert!(events.iter().any(|event| match event {
+            Event::GameOver(_) => true,
+            _ => false,
+        }));
+    }
+
+    #[test]
+    fn nobody_is_shown_once_the_game_is_over() {
+        let mut game = Game::new();
+        while !game.is_game_over() {
+            die(&mut game);
+        }
+        assert!(!game.is_visible(Actor::PacMan));
+        for &personality in &Personality::ALL {
+            assert!(!game.is_visible(Actor::Ghost(personality)));
+        }
+    }
+
+    #[test]
+    fn fruit_appears_at_seventy_and_a_hundred_and_seventy_dots() {
+        for &dots in &BONUS_DOTS {
+            let mut game = Game::new();
+            let events = eat_dots(&mut game, dots);
+            assert!(events.iter().any(|event| match event {
+                Event::BonusShown(shown) => shown.fruit == Fruit::Cherries,
+                _ => false,
+            }));
+            assert!(game.bonus().is_some());
+        }
+    }
+
+    #[test]
+    fn no_fruit_before_seventy_dots() {
+        let mut game = Game::new();
+        eat_dots(&mut game, BONUS_DOTS[0] - 1);
+        assert!(game.bonus().is_none());
+    }
+
+    // Clear dots from the board until only `left` remain.
+    fn leave_dots(game: &mut Game, left: usize) {
+        for x in 0..TILES_WIDE as u32 {
+            for y in 0..TILES_HIGH as u32 {
+                if game.map.dots_remaining() == left {
+                    return;
+                }
+                game.map.remove_dot(x, y);
+            }
+        }
+    }
+
+    #[test]
+    fn elroy_waits_for_clyde_after_a_death() {
+        let mut game = Game::new();
+        leave_dots(&mut game, 21);
+        assert!(game.elroy().is_none());
+        leave_dots(&mut game, 20);
+        assert_eq!(game.elroy().map(|stage| stage.speed), Some(80));
+        die(&mut game);
+        assert!(game.elroy().is_none());
+        game.ghost_mut(Personality::Clyde).release();
+        game.tick(None);
+        assert_eq!(game.elroy().map(|stage| stage.speed), Some(80));
+    }
+}
@@ -0,0 +1,175 @@
+use super::level::{FrightTime, Level};
+
+/// What the ghosts are collectively up to, outside of being frightened.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum GhostMode {
+    /// Each ghost heads for its own corner.
+    Scatter,
+    /// Each ghost hunts PacMan with its own targeting strategy.
+    Chase,
+}
+
+/// The global timer that flips the ghosts between scatter and chase on the level's
+/// schedule. It doesn't run while the ghosts are frightened.
+pub(crate) struct ModeTimer {
+    schedule: &'static [u32],
+    phase: usize,
+    ticks_left: u32,
+}
+
+impl ModeTimer {
+    pub fn new(level: &Level) -> Self {
+        let schedule = level.mode_schedule();
+        Self {
+            schedule,
+            phase: 0,
+            ticks_left: schedule.first().copied().unwrap_or(0),
+        }
+    }
+
+    pub fn mode(&self) -> GhostMode {
+        if self.phase % 2 == 0 && self.phase < self.schedule.len() {
+            GhostMode::Scatter
+        } else {
+            GhostMode::Chase
+        }
+    }
+
+    /// Which phase of the schedule we're in, counting from 0.
+    pub fn phase(&self) -> usize {
+        self.phase
+    }
+
+    /// Ticks until the next switch, or None once the ghosts chase for good.
+    pub fn ticks_left(&self) -> Option<u32> {
+        if self.phase < self.schedule.len() {
+            Some(self.ticks_left)
+        } else {
+            None
+        }
+    }
+
+    /// Advance one tick, returning the new mode if it changed.
+    pub fn tick(&mut self) -> Option<GhostMode> {
+        if self.phase >= self.schedule.len() {
+            return None;
+        }
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+        if self.ticks_left > 0 {
+            return None;
+        }
+        self.phase += 1;
+        self.ticks_left = self.schedule.get(self.phase).copied().unwrap_or(0);
+        Some(self.mode())
+    }
+}
+
+// Ticks the ghosts spend white (then blue) in each warning flash.
+const FLASH_TICKS: u32 = 14;
+
+/// Counts down an energizer's effect. The ghosts flash during the last few seconds.
+pub(crate) struct FrightTimer {
+    ticks_left: u32,
+    flash_ticks: u32,
+}
+
+impl FrightTimer {
+    pub fn new(fright_time: &FrightTime) -> Self {
+        Self {
+            ticks_left: fright_time.ticks,
+            flash_ticks: (fright_time.flashes * 2 * FLASH_TICKS).min(fright_time.ticks),
+        }
+    }
+
+    pub fn ticks_left(&self) -> u32 {
+        self.ticks_left
+    }
+
+    /// Whether frightened ghosts should be showing white right now.
+    pub fn is_white(&self) -> bool {
+        if self.ticks_left > self.flash_ticks {
+            return false;
+        }
+        let into_warning = self.flash_ticks - self.ticks_left;
+        (into_warning / FLASH_TICKS) % 2 == 0
+    }
+
+    /// Advance one tick, returning true once the fright has worn off.
+    pub fn tick(&mut self) -> bool {
+        self.ticks_left = self.ticks_left.saturating_sub(1);
+        self.ticks_left == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mode_timer_follows_the_schedule() {
+        let level = Level::default();
+        let schedule = level.mode_schedule();
+        let mut timer = ModeTimer::new(&level);
+        assert_eq!(timer.mode(), GhostMode::Scatter);
+        assert_eq!(timer.ticks_left(), Some(schedule[0]));
+
+        for _ in 1..schedule[0] {
+            assert_eq!(timer.tick(), None);
+        }
+        assert_eq!(timer.ticks_left(), Some(1));
+        assert_eq!(timer.tick(), Some(GhostMode::Chase));
+        assert_eq!(timer.phase(), 1);
+        assert_eq!(timer.ticks_left(), Some(schedule[1]));
+
+        for _ in 1..schedule[1] {
+            assert_eq!(timer.tick(), None);
+        }
+        assert_eq!(timer.tick(), Some(GhostMode::Scatter));
+        assert_eq!(timer.phase(), 2);
+    }
+
+    #[test]
+    fn mode_timer_chases_for_good_after_the_schedule() {
+        let level = Level::default();
+        let mut timer = ModeTimer::new(&level);
+        let total: u32 = level.mode_schedule().iter().sum();
+        let switches = (0..total).filter_map(|_| timer.tick()).count();
+        assert_eq!(switches, level.mode_schedule().len());
+        assert_eq!(timer.mode(), GhostMode::Chase);
+        assert_eq!(timer.ticks_left(), None);
+        assert_eq!(timer.tick(), None);
+        assert_eq!(timer.mode(), GhostMode::Chase);
+    }
+
+    #[test]
+    fn fright_timer_flashes_before_wearing_off() {
+        let mut timer = FrightTimer::new(&FrightTime { ticks: 360, flashes: 5 });
+        let warning = 5 * 2 * FLASH_TICKS;
+        while timer.ticks_left() > warning {
+            assert!(!timer.is_white());
+            assert!(!timer.tick());
+        }
+        for flash in 0..5 {
+            for _ in 0..FLASH_TICKS {
+                assert!(timer.is_white(), "flash {}", flash);
+                timer.tick();
+            }
+            for _ in 0..FLASH_TICKS {
+                assert!(!timer.is_white(), "flash {}", flash);
+                if timer.tick() {
+                    assert_eq!(flash, 4);
+                }
+            }
+        }
+        assert_eq!(timer.ticks_left(), 0);
+    }
+
+    #[test]
+    fn fright_timer_flashes_no_longer_than_it_lasts() {
+        let mut timer = FrightTimer::new(&FrightTime { ticks: 60, flashes: 5 });
+        // The warning takes up the whole fright, starting on white.
+        assert!(timer.is_white());
+        let ticks = (0..).take_while(|_| !timer.tick()).count();
+        assert_eq!(ticks, 59);
+    }
+}
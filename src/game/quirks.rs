@@ -0,0 +1,20 @@
+/// Bugs in the original arcade code that change how the game plays. They're on by default
+/// so the ghosts behave the way players expect; switch them off for the "intended" game.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct Quirks {
+    /// Looking "ahead" of PacMan while he faces up also shifts the same number of tiles
+    /// left, thanks to an overflow in the arcade's offset math. Affects Pinky and Inky.
+    pub up_overflow: bool,
+    /// Collisions are only checked by tile, so PacMan and a ghost that swap tiles in the
+    /// same tick pass right through each other.
+    pub pass_through: bool,
+}
+
+impl Default for Quirks {
+    fn default() -> Self {
+        Self {
+            up_overflow: true,
+            pass_through: true,
+        }
+    }
+}
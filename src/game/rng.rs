@@ -0,0 +1,30 @@
+/// The arcade's pseudo-random number generator, used to pick turns for frightened ghosts
+/// (and, with its own instance, how long the bonus fruit stays out).
+/// It's reset to the same seed at the start of every level (and every life), which is
+/// what makes the blue ghosts' wandering repeatable enough for patterns to work.
+pub(crate) struct Prng {
+    index: u16,
+}
+
+impl Default for Prng {
+    fn default() -> Self {
+        Self { index: 0 }
+    }
+}
+
+impl Prng {
+    /// The next value in 0..4.
+    ///
+    /// The arcade steps `index = index * 5 + 1` (mod 8192) and reads the byte at that
+    /// address of its own program ROM. We don't ship the ROM, so we use the top bits of the
+    /// index instead; the low bits of this generator just count 0, 1, 2, 3.
+    pub fn next_direction_bits(&mut self) -> u16 {
+        self.next() >> 11
+    }
+
+    /// The next raw index, in 0..8192.
+    pub fn next(&mut self) -> u16 {
+        self.index = self.index.wrapping_mul(5).wrapping_add(1) & 0x1fff;
+        self.index
+    }
+}
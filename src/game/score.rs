@@ -0,0 +1,36 @@
+use super::map::DotKind;
+
+const DOT_POINTS: u32 = 10;
+const ENERGIZER_POINTS: u32 = 50;
+
+// Each ghost eaten on the same energizer is worth double the last.
+const GHOST_POINTS: [u32; 4] = [200, 400, 800, 1600];
+
+#[derive(Default)]
+pub(crate) struct Score {
+    points: u32,
+}
+
+impl Score {
+    pub fn add(&mut self, points: u32) {
+        self.points += points;
+    }
+
+    pub fn add_dot(&mut self, kind: DotKind) {
+        self.add(match kind {
+            DotKind::Dot => DOT_POINTS,
+            DotKind::Energizer => ENERGIZER_POINTS,
+        });
+    }
+
+    /// Score the `chain`th ghost (from 0) eaten on the current energizer, returning its value.
+    pub fn add_ghost(&mut self, chain: u32) -> u32 {
+        let points = GHOST_POINTS[(chain as usize).min(GHOST_POINTS.len() - 1)];
+        self.add(points);
+        points
+    }
+
+    pub fn points(&self) -> u32 {
+        self.points
+    }
+}
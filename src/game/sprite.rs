@@ -0,0 +1,235 @@
+use super::map::{Map, TileInformation};
+use super::consts::{Direction, TILES_WIDE, TILE_SIZE};
+
+// Default number of ticks each animation frame is shown for.
+const ANIMATION_TICKS: u32 = 2;
+
+/// Position, movement and animation state shared by everything that moves through the maze.
+pub(crate) struct Sprite {
+    facing: Direction,
+    // In 1/256ths of a pixel per tick.
+    speed: u32,
+    subpixels: u32,
+    texture_indexes: Vec<u32>,
+    animating: bool,
+    animation_tick: u32,
+    animation_ticks: u32,
+    animation_countdown: u32,
+    tile_info: TileInformation,
+    // Where the sprite was at the start of the last tick, for interpolated rendering.
+    previous_tile_info: TileInformation,
+    paused_ticks: u32,
+}
+
+impl Sprite {
+    pub fn update(
+        &mut self,
+        speed: u32,
+        facing: Direction,
+        indexes: Vec<u32>) {
+        self.speed = speed;
+        if facing != self.facing {
+            self.facing = facing;
+            self.texture_indexes = indexes;
+            self.animation_tick = 0;
+        }
+    }
+
+    pub fn new(tile_info: TileInformation, direction: Direction, indexes: Vec<u32>) -> Self {
+        Self {
+            facing: direction,
+            speed: 0,
+            subpixels: 0,
+            texture_indexes: indexes,
+            animating: false,
+            animation_tick: 0,
+            animation_ticks: ANIMATION_TICKS,
+            animation_countdown: ANIMATION_TICKS,
+            tile_info,
+            previous_tile_info: tile_info,
+            paused_ticks: 0,
+        }
+    }
+
+    pub fn with_animation_ticks(mut self, ticks: u32) -> Self {
+        self.animation_ticks = ticks;
+        self.animation_countdown = ticks;
+        self
+    }
+
+    /// Swap the animation frames without turning, e.g. when a ghost turns blue.
+    pub fn set_texture_indexes(&mut self, indexes: Vec<u32>) {
+        if indexes != self.texture_indexes {
+            self.animation_tick %= indexes.len() as u32;
+            self.texture_indexes = indexes;
+        }
+    }
+
+    pub fn set_speed(&mut self, speed: u32) {
+        self.speed = speed;
+    }
+
+    /// Hold the sprite in place for the next `ticks` simulation ticks.
+    pub fn pause(&mut self, ticks: u32) {
+        self.paused_ticks += ticks;
+    }
+
+    /// Stay where it is for a tick it doesn't move, so it's drawn standing still rather than
+    /// still sliding in from where it last was.
+    pub fn hold(&mut self) {
+        self.previous_tile_info = self.tile_info;
+    }
+
+    pub fn facing(&self) -> Direction {
+        self.facing
+    }
+
+    pub fn speed(&self) -> u32 {
+        self.speed
+    }
+
+    pub fn tile_info(&self) -> TileInformation {
+        self.tile_info
+    }
+
+    pub fn previous_tile_info(&self) -> TileInformation {
+        self.previous_tile_info
+    }
+
+    pub fn texture_index(&self) -> u32 {
+        self.texture_indexes[self.animation_tick as usize]
+    }
+
+    /// Advance the sprite by one simulation tick: whole-pixel movement, then animation.
+    pub fn tick(&mut self, map: &Map) {
+        self.tick_steered(map, |_| true);
+    }
+
+    /// Like `tick`, but `steer` gets a chance to turn the sprite before every pixel it moves,
+    /// so turns can happen exactly at tile centers. It returns false to stop short for this
+    /// tick.
+    pub fn tick_steered(&mut self, map: &Map, mut steer: impl FnMut(&mut Sprite) -> bool) {
+        self.previous_tile_info = self.tile_info;
+        if self.paused_ticks > 0 {
+            self.paused_ticks -= 1;
+        } else {
+            self.subpixels += self.speed;
+            while self.subpixels >= 256 {
+                self.subpixels -= 256;
+                if !steer(self) || !self.step(map) {
+                    self.subpixels = 0;
+                    break;
+                }
+            }
+        }
+        self.animate();
+    }
+
+    /// Like `tick_steered`, but ignoring the map, for scripted paths such as the inside of the
+    /// ghost house.
+    pub fn tick_unchecked(&mut self, mut steer: impl FnMut(&mut Sprite) -> bool) {
+        self.previous_tile_info = self.tile_info;
+        if self.paused_ticks > 0 {
+            self.paused_ticks -= 1;
+        } else {
+            self.subpixels += self.speed;
+            while self.subpixels >= 256 {
+                self.subpixels -= 256;
+                if !steer(self) {
+                    self.subpixels = 0;
+                    break;
+                }
+                if let Some(tile_info) = self.tile_info.neighbor_pixel(self.facing) {
+                    self.tile_info = tile_info;
+                }
+                self.animating = true;
+            }
+        }
+        self.animate();
+    }
+
+    // Move a single pixel in the facing direction. Returns false if a wall is in the way.
+    fn step(&mut self, map: &Map) -> bool {
+        let new_tile_info = match self.tile_info.neighbor_pixel(self.facing) {
+            Some(tile_info) => tile_info,
+            None => {
+                self.animating = false;
+                return false;
+            },
+        };
+        let (x, y) = new_tile_info.tile();
+
+        // Have to check if the offset is beyond center, then grab adjacent tile and see if it's valid.
+        let is_valid_move = map.try_tile(x, y).map_or(false, |_| {
+            if new_tile_info.past_center(self.facing) {
+                map.get_adjacent(x, y, self.facing).map_or(false, |(_, t)| t.is_valid_path())
+            } else {
+                true
+            }
+        });
+        if is_valid_move {
+            // Pull toward the center line of the tile while moving -- this is "cornering"
+            let new_tile_info = adjust_for_cornering(new_tile_info, self.facing);
+
+            // Jump tiles if we're teleporting.
+            let (new_tile_info, teleported) = tunnel_teleport(map, new_tile_info, self.facing);
+
+            // Okay, update the position valid to move into this tile..
+            self.tile_info = new_tile_info;
+            if teleported {
+                self.previous_tile_info = new_tile_info;
+            }
+            self.animating = true;
+        } else {
+            // Bumped against a wall. We stop.
+            self.animating = false;
+        }
+        is_valid_move
+    }
+
+    fn animate(&mut self) {
+        if !self.animating {
+            return;
+        }
+        self.animation_countdown -= 1;
+        if self.animation_countdown == 0 {
+            self.animation_countdown = self.animation_ticks;
+            // Cycle to the next item in the sequence, wrapping.
+            self.animation_tick =
+                (self.animation_tick + 1) % (self.texture_indexes.len() as u32);
+        }
+    }
+}
+
+fn adjust_for_cornering(
+    tile_info: TileInformation,
+    facing: Direction,
+) -> TileInformation {
+    if let Some(tweak) = tile_info.align(facing) {
+        tile_info.neighbor_pixel(tweak).unwrap_or(tile_info)
+    } else {
+        tile_info
+    }
+}
+
+// Reaching the edge of a row that wraps puts us back in at the other side.
+fn tunnel_teleport(
+    map: &Map,
+    tile_info: TileInformation,
+    facing: Direction,
+) -> (TileInformation, bool) {
+    let teleport_distance = (TILES_WIDE - 2) as u32 * TILE_SIZE as u32;
+    let (x, y) = tile_info.pixels();
+    if !map.wraps(tile_info.y()) {
+        return (tile_info, false);
+    }
+    match (facing, tile_info.edge()) {
+        (Direction::Right, Some(Direction::Right)) => {
+            (TileInformation::from_pixels(x - teleport_distance, y), true)
+        },
+        (Direction::Left, Some(Direction::Left)) => {
+            (TileInformation::from_pixels(x + teleport_distance, y), true)
+        },
+        _ => (tile_info, false),
+    }
+}
@@ -0,0 +1,181 @@
+//! Where each ghost heads while chasing. Movement is the same for every ghost (see
+//! `ghost::choose_direction`); only the target tile differs, so each personality is just
+//! a `Targeting` strategy. Targets are signed because they can fall off the map.
+
+use super::consts::Direction;
+use super::ghost::Personality;
+use super::quirks::Quirks;
+use super::sprite::Sprite;
+
+/// What a ghost can see when picking a target.
+pub(crate) struct Board<'a> {
+    pub ghost: &'a Sprite,
+    pub pacman: &'a Sprite,
+    pub blinky: &'a Sprite,
+}
+
+pub(crate) trait Targeting: Send + Sync {
+    fn target(&self, board: &Board) -> (i32, i32);
+}
+
+/// The arcade's chase strategy for each ghost.
+pub(crate) fn chase_strategy(
+    personality: Personality,
+    quirks: &Quirks,
+) -> Box<dyn Targeting> {
+    match personality {
+        Personality::Blinky => Box::new(Shadow),
+        Personality::Pinky => Box::new(Speedy { up_overflow: quirks.up_overflow }),
+        Personality::Inky => Box::new(Bashful { up_overflow: quirks.up_overflow }),
+        Personality::Clyde => Box::new(Pokey { corner: personality.scatter_target() }),
+    }
+}
+
+/// Blinky goes straight for PacMan's tile.
+pub(crate) struct Shadow;
+
+impl Targeting for Shadow {
+    fn target(&self, board: &Board) -> (i32, i32) {
+        tile(board.pacman)
+    }
+}
+
+/// Pinky aims four tiles in front of PacMan to cut him off.
+pub(crate) struct Speedy {
+    pub up_overflow: bool,
+}
+
+impl Targeting for Speedy {
+    fn target(&self, board: &Board) -> (i32, i32) {
+        ahead(board.pacman, 4, self.up_overflow)
+    }
+}
+
+/// Inky takes the tile two in front of PacMan and doubles the vector to it from Blinky.
+pub(crate) struct Bashful {
+    pub up_overflow: bool,
+}
+
+impl Targeting for Bashful {
+    fn target(&self, board: &Board) -> (i32, i32) {
+        let (pivot_x, pivot_y) = ahead(board.pacman, 2, self.up_overflow);
+        let (blinky_x, blinky_y) = tile(board.blinky);
+        (2 * pivot_x - blinky_x, 2 * pivot_y - blinky_y)
+    }
+}
+
+/// Clyde chases like Blinky until he's within eight tiles of PacMan, then loses his nerve
+/// and heads for his corner.
+pub(crate) struct Pokey {
+    pub corner: (i32, i32),
+}
+
+impl Targeting for Pokey {
+    fn target(&self, board: &Board) -> (i32, i32) {
+        let pacman = tile(board.pacman);
+        if distance_squared(tile(board.ghost), pacman) >= 8 * 8 {
+            pacman
+        } else {
+            self.corner
+        }
+    }
+}
+
+pub(crate) fn distance_squared(a: (i32, i32), b: (i32, i32)) -> i32 {
+    let dx = a.0 - b.0;
+    let dy = a.1 - b.1;
+    dx * dx + dy * dy
+}
+
+fn tile(sprite: &Sprite) -> (i32, i32) {
+    let (x, y) = sprite.tile_info().tile();
+    (x as i32, y as i32)
+}
+
+fn ahead(sprite: &Sprite, tiles: i32, up_overflow: bool) -> (i32, i32) {
+    let (x, y) = tile(sprite);
+    match sprite.facing() {
+        Direction::Up if up_overflow => (x - tiles, y + tiles),
+        Direction::Up => (x, y + tiles),
+        Direction::Down => (x, y - tiles),
+        Direction::Left => (x - tiles, y),
+        Direction::Right => (x + tiles, y),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::game::map::TileInformation;
+
+    fn sprite_at(x: u32, y: u32, facing: Direction) -> Sprite {
+        Sprite::new(TileInformation::from((x, y, 4, 4)), facing, vec![0])
+    }
+
+    fn target(
+        targeting: &dyn Targeting,
+        ghost: &Sprite,
+        pacman: &Sprite,
+        blinky: &Sprite,
+    ) -> (i32, i32) {
+        targeting.target(&Board { ghost, pacman, blinky })
+    }
+
+    #[test]
+    fn blinky_targets_pacman() {
+        let pacman = sprite_at(10, 12, Direction::Left);
+        let ghost = sprite_at(20, 27, Direction::Left);
+        assert_eq!(target(&Shadow, &ghost, &pacman, &ghost), (10, 12));
+    }
+
+    #[test]
+    fn pinky_targets_four_ahead() {
+        let ghost = sprite_at(20, 27, Direction::Left);
+        let speedy = Speedy { up_overflow: false };
+        for &(facing, expected) in &[
+            (Direction::Left, (6, 12)),
+            (Direction::Right, (14, 12)),
+            (Direction::Down, (10, 8)),
+            (Direction::Up, (10, 16)),
+        ] {
+            let pacman = sprite_at(10, 12, facing);
+            assert_eq!(target(&speedy, &ghost, &pacman, &ghost), expected, "{:?}", facing);
+        }
+    }
+
+    #[test]
+    fn up_overflow_shifts_left_too() {
+        let ghost = sprite_at(20, 27, Direction::Left);
+        let pacman = sprite_at(10, 12, Direction::Up);
+        let speedy = Speedy { up_overflow: true };
+        assert_eq!(target(&speedy, &ghost, &pacman, &ghost), (6, 16));
+        // Only facing up is affected.
+        let pacman = sprite_at(10, 12, Direction::Right);
+        assert_eq!(target(&speedy, &ghost, &pacman, &ghost), (14, 12));
+    }
+
+    #[test]
+    fn inky_doubles_the_vector_from_blinky() {
+        let ghost = sprite_at(20, 27, Direction::Left);
+        let pacman = sprite_at(10, 12, Direction::Right);
+        let blinky = sprite_at(8, 10, Direction::Left);
+        // Two ahead of PacMan is (12, 12); Blinky is (4, 2) away from that.
+        let bashful = Bashful { up_overflow: false };
+        assert_eq!(target(&bashful, &ghost, &pacman, &blinky), (16, 14));
+
+        let pacman = sprite_at(10, 12, Direction::Up);
+        let bashful = Bashful { up_overflow: true };
+        // The pivot is (8, 14) with the overflow.
+        assert_eq!(target(&bashful, &ghost, &pacman, &blinky), (8, 18));
+    }
+
+    #[test]
+    fn clyde_gives_up_within_eight_tiles() {
+        let pokey = Pokey { corner: Personality::Clyde.scatter_target() };
+        let pacman = sprite_at(10, 12, Direction::Left);
+        let far = sprite_at(18, 12, Direction::Left);
+        assert_eq!(target(&pokey, &far, &pacman, &far), (10, 12));
+        let near = sprite_at(17, 12, Direction::Left);
+        assert_eq!(target(&pokey, &near, &pacman, &near), Personality::Clyde.scatter_target());
+    }
+}
@@ -0,0 +1,41 @@
+use bevy::prelude::*;
+use crate::consts::SCALE;
+use crate::game::{Actor, Game};
+use crate::map::tile_translation;
+use crate::sprite::load_sprite_sheet;
+
+pub(crate) struct GhostPlugin;
+
+impl Plugin for GhostPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .add_startup_system(setup_ghosts.system())
+        ;
+    }
+}
+
+fn setup_ghosts(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game: Res<Game>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let ghost_texture_atlas_handle = load_sprite_sheet(
+        &asset_server, &mut textures, &mut texture_atlases);
+    for ghost in game.ghosts() {
+        let mut translation = tile_translation(ghost.sprite().tile_info());
+        translation.set_z(1.5); // Ghosts pass over PacMan.
+        let mut sprite_sheet_components = SpriteSheetComponents {
+            texture_atlas: ghost_texture_atlas_handle,
+            transform: Transform::from_scale(SCALE)
+                .with_translation(translation),
+            ..Default::default()
+        };
+        sprite_sheet_components.sprite.index = ghost.sprite().texture_index();
+        commands
+            .spawn(sprite_sheet_components)
+            .with(Actor::Ghost(ghost.personality()))
+        ;
+    }
+}
@@ -0,0 +1,31 @@
+use crate::game::Game;
+use crate::game::TICKS_PER_SECOND;
+use crate::game::autopilot::Autopilot;
+
+// Give up on a game that hasn't finished after this much simulated time.
+const MAX_TICKS: u64 = 60 * 60 * TICKS_PER_SECOND as u64;
+
+/// Play `games` games without a window, with the autopilot at the controls. Each game gets
+/// its own seed, so each plays out differently.
+pub(crate) fn run(games: u32) {
+    let mut unfinished = 0;
+    for number in 1..=games {
+        let mut game = Game::new();
+        let mut autopilot = Autopilot::with_seed(number);
+        while !game.is_game_over() && game.ticks() < MAX_TICKS {
+            let input = autopilot.steer(&game);
+            game.tick(input);
+            game.drain_events();
+        }
+        if !game.is_game_over() {
+            unfinished += 1;
+        }
+        println!("Game {}: score {} after {} ticks (level {}, {} dots left)",
+                 number, game.score().points(), game.ticks(), game.level().number(),
+                 game.map().dots_remaining());
+    }
+    if unfinished > 0 {
+        eprintln!("{} of {} games did not finish", unfinished, games);
+        std::process::exit(1);
+    }
+}
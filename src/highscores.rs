@@ -0,0 +1,186 @@
+//! The top ten table, kept in a plain text file under the XDG data directory: one
+//! `INITIALS POINTS` line per entry. Whatever can't be read is quietly dropped, so a missing
+//! or mangled file just means a shorter (or empty) table.
+
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+pub(crate) const MAX_ENTRIES: usize = 10;
+pub(crate) const INITIALS: usize = 3;
+
+const DATA_DIR: &str = "rpm";
+const FILE_NAME: &str = "highscores.txt";
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct HighScore {
+    pub initials: String,
+    pub points: u32,
+}
+
+/// The best scores, highest first.
+#[derive(Default)]
+pub(crate) struct HighScores {
+    entries: Vec<HighScore>,
+}
+
+impl HighScores {
+    /// Read the table from disk, falling back to an empty one.
+    pub fn load() -> Self {
+        scores_path()
+            .and_then(|path| fs::read(path).ok())
+            .map_or_else(Self::default, |bytes| Self::parse(&bytes))
+    }
+
+    fn parse(bytes: &[u8]) -> Self {
+        let mut entries: Vec<_> = String::from_utf8_lossy(bytes)
+            .lines()
+            .filter_map(|line| {
+                let mut fields = line.split_whitespace();
+                let initials = fields.next()?;
+                let points = fields.next()?.parse().ok()?;
+                let valid = fields.next().is_none()
+                    && initials.len() == INITIALS
+                    && initials.chars().all(|ch| ch.is_ascii_uppercase());
+                if valid {
+                    Some(HighScore { initials: initials.to_string(), points })
+                } else {
+                    None
+                }
+            })
+            .collect();
+        entries.sort_by(|a, b| b.points.cmp(&a.points));
+        entries.truncate(MAX_ENTRIES);
+        Self { entries }
+    }
+
+    /// Write the table out, replacing the old file in one go so a crash part way through
+    /// can't leave it half written.
+    pub fn save(&self) -> io::Result<()> {
+        let path = scores_path()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no data directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let contents: String = self.entries
+            .iter()
+            .map(|entry| format!("{} {}\n", entry.initials, entry.points))
+            .collect();
+        let temp = path.with_extension("tmp");
+        fs::write(&temp, contents)?;
+        fs::rename(&temp, &path)
+    }
+
+    pub fn entries(&self) -> &[HighScore] {
+        &self.entries
+    }
+
+    pub fn best(&self) -> u32 {
+        self.entries.first().map_or(0, |entry| entry.points)
+    }
+
+    /// Whether `points` would make it onto the table.
+    pub fn qualifies(&self, points: u32) -> bool {
+        points > 0 && (self.entries.len() < MAX_ENTRIES
+            || self.entries.last().map_or(true, |last| points > last.points))
+    }
+
+    /// Add a score, below any equal ones already there.
+    pub fn insert(&mut self, initials: String, points: u32) {
+        let at = self.entries
+            .iter()
+            .position(|entry| entry.points < points)
+            .unwrap_or_else(|| self.entries.len());
+        self.entries.insert(at, HighScore { initials, points });
+        self.entries.truncate(MAX_ENTRIES);
+    }
+}
+
+// $XDG_DATA_HOME/rpm/highscores.txt, or under ~/.local/share when that isn't set.
+fn scores_path() -> Option<PathBuf> {
+    let data_home = env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .filter(|path| path.is_absolute())
+        .or_else(|| env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))?;
+    Some(data_home.join(DATA_DIR).join(FILE_NAME))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn table(scores: &HighScores) -> Vec<(&str, u32)> {
+        scores.entries().iter().map(|entry| (entry.initials.as_str(), entry.points)).collect()
+    }
+
+    // A full table holding 1000, 900, ... 100.
+    fn full() -> HighScores {
+        let contents: String = (1..=MAX_ENTRIES as u32)
+            .map(|rank| format!("AAA {}\n", (MAX_ENTRIES as u32 + 1 - rank) * 100))
+            .collect();
+        HighScores::parse(contents.as_bytes())
+    }
+
+    #[test]
+    fn parse_drops_junk_lines() {
+        let scores = HighScores::parse(b"ABC 100\n\nnonsense\nDEF\nGHI lots\n  JKL   200  \n");
+        assert_eq!(table(&scores), [("JKL", 200), ("ABC", 100)]);
+    }
+
+    #[test]
+    fn parse_drops_bad_initials() {
+        let scores = HighScores::parse(b"abc 100\nAB 200\nABCD 300\nA1C 400\nXYZ 500\n");
+        assert_eq!(table(&scores), [("XYZ", 500)]);
+    }
+
+    #[test]
+    fn parse_drops_lines_with_extra_fields() {
+        let scores = HighScores::parse(b"ABC 100 200\nDEF 300\n");
+        assert_eq!(table(&scores), [("DEF", 300)]);
+    }
+
+    #[test]
+    fn parse_sorts_and_keeps_the_best_ten() {
+        let contents: String = (1..=15).map(|points| format!("AAA {}\n", points)).collect();
+        let scores = HighScores::parse(contents.as_bytes());
+        let points: Vec<u32> = scores.entries().iter().map(|entry| entry.points).collect();
+        assert_eq!(points, (6..=15).rev().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn parse_survives_bytes_that_are_not_utf8() {
+        let scores = HighScores::parse(b"ABC 100\n\xff\xfe 200\nD\xc0F 300\nGHI 400\n");
+        assert_eq!(table(&scores), [("GHI", 400), ("ABC", 100)]);
+    }
+
+    #[test]
+    fn insert_goes_below_equal_scores() {
+        let mut scores = HighScores::parse(b"AAA 300\nBBB 200\nCCC 100\n");
+        scores.insert("DDD".to_string(), 200);
+        assert_eq!(table(&scores), [("AAA", 300), ("BBB", 200), ("DDD", 200), ("CCC", 100)]);
+    }
+
+    #[test]
+    fn insert_keeps_the_best_ten() {
+        let mut scores = full();
+        scores.insert("ZZZ".to_string(), 550);
+        assert_eq!(scores.entries().len(), MAX_ENTRIES);
+        assert_eq!(scores.entries()[5], HighScore { initials: "ZZZ".to_string(), points: 550 });
+        assert_eq!(scores.entries().last().unwrap().points, 200);
+    }
+
+    #[test]
+    fn any_score_qualifies_for_a_short_table() {
+        let scores = HighScores::parse(b"AAA 1000\n");
+        assert!(scores.qualifies(1));
+        assert!(!scores.qualifies(0));
+    }
+
+    #[test]
+    fn a_full_table_needs_more_than_its_lowest_score() {
+        let scores = full();
+        assert!(!scores.qualifies(100));
+        assert!(scores.qualifies(101));
+    }
+}
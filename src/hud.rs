@@ -0,0 +1,191 @@
+use bevy::prelude::*;
+
+use crate::consts::SCALE;
+use crate::game::Game;
+use crate::game::bonus::FRUIT_HISTORY;
+use crate::highscores::HighScores;
+use crate::map::map_tile_to_translation;
+use crate::sprite::load_sprite_sheet;
+use crate::state::CurrentState;
+use crate::text::{TextColor, glyph_index, load_font, spawn_text};
+
+// Above the maze, though nothing overlaps.
+const HUD_Z: f32 = 1.0;
+
+// Top two rows: the labels, with the scores right-aligned underneath.
+const ONE_UP_TILE: (u32, u32) = (5, 35);
+const HIGH_SCORE_LABEL_TILE: (u32, u32) = (11, 35);
+const SCORE_RIGHT_TILE: (u32, u32) = (8, 34);
+const HIGH_SCORE_RIGHT_TILE: (u32, u32) = (18, 34);
+const SCORE_DIGITS: u32 = 7;
+
+// 1UP blinks on and off while the game is going.
+const ONE_UP_BLINK_TICKS: u64 = 16;
+
+// Bottom row: spare lives fill from the left and the fruit row from the right, each icon
+// two tiles wide.
+const LIFE_ICONS: u32 = 5;
+const LIFE_ICON_LEFT_TILE: u32 = 5;
+const LIFE_ICON_INDEX: u32 = 14;
+const FRUIT_HISTORY_RIGHT_TILE: u32 = 27;
+const BOTTOM_ROW_TILE_Y: u32 = 1;
+
+#[derive(Copy, Clone, PartialEq, Eq)]
+enum ScoreField {
+    Player,
+    High,
+}
+
+/// One digit of a score, `place` being its power of ten.
+struct ScoreDigit {
+    field: ScoreField,
+    place: u32,
+}
+
+/// Marks the letters of the blinking 1UP label.
+#[derive(Clone)]
+struct OneUp;
+
+/// Marks the static labels.
+#[derive(Clone)]
+struct Label;
+
+/// A spare life along the bottom, counting from the left.
+struct LifeIcon(u32);
+
+/// One place in the fruit row, counting from the right.
+struct FruitSlot(usize);
+
+pub(crate) struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .add_startup_system(setup_hud.system())
+            .add_system(show_scores.system())
+            .add_system(blink_one_up.system())
+            .add_system(show_lives.system())
+            .add_system(show_fruit_history.system())
+        ;
+    }
+}
+
+fn setup_hud(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let font = load_font(&asset_server, &mut textures, &mut texture_atlases);
+    let sheet = load_sprite_sheet(&asset_server, &mut textures, &mut texture_atlases);
+    spawn_text(&mut commands, font, "1UP", TextColor::White, ONE_UP_TILE, HUD_Z, OneUp);
+    spawn_text(
+        &mut commands, font, "HIGH SCORE", TextColor::White,
+        HIGH_SCORE_LABEL_TILE, HUD_Z, Label);
+
+    for &(field, (right, y)) in [
+        (ScoreField::Player, SCORE_RIGHT_TILE),
+        (ScoreField::High, HIGH_SCORE_RIGHT_TILE),
+    ].iter() {
+        for place in 0..SCORE_DIGITS {
+            let translation = map_tile_to_translation(right - place, y, None, None);
+            commands
+                .spawn(hud_sprite(font, translation))
+                .with(ScoreDigit { field, place })
+            ;
+        }
+    }
+
+    for slot in 0..LIFE_ICONS {
+        let x = LIFE_ICON_LEFT_TILE + 2 * slot;
+        let translation = map_tile_to_translation(x, BOTTOM_ROW_TILE_Y, Some(0), Some(0));
+        let mut sprite_sheet_components = hud_sprite(sheet, translation);
+        sprite_sheet_components.sprite.index = LIFE_ICON_INDEX;
+        commands
+            .spawn(sprite_sheet_components)
+            .with(LifeIcon(slot))
+        ;
+    }
+
+    for slot in 0..FRUIT_HISTORY {
+        let x = FRUIT_HISTORY_RIGHT_TILE - 2 * slot as u32;
+        let translation = map_tile_to_translation(x, BOTTOM_ROW_TILE_Y, Some(0), Some(0));
+        commands
+            .spawn(hud_sprite(sheet, translation))
+            .with(FruitSlot(slot))
+        ;
+    }
+}
+
+// Everything that changes starts out hidden until the first update.
+fn hud_sprite(atlas: Handle<TextureAtlas>, mut translation: Vec3) -> SpriteSheetComponents {
+    translation.set_z(HUD_Z);
+    let mut sprite_sheet_components = SpriteSheetComponents {
+        texture_atlas: atlas,
+        transform: Transform::from_scale(SCALE)
+            .with_translation(translation),
+        ..Default::default()
+    };
+    sprite_sheet_components.draw.is_visible = false;
+    sprite_sheet_components
+}
+
+// Scores are drawn without leading zeros, but always with at least two digits, as the
+// arcade does: every score is a multiple of ten. Demo games don't count toward the high score.
+fn show_scores(
+    game: Res<Game>,
+    high_scores: Res<HighScores>,
+    current: Res<CurrentState>,
+    mut query: Query<(&ScoreDigit, &mut TextureAtlasSprite, &mut Draw)>,
+) {
+    for (digit, mut atlas_sprite, mut draw) in &mut query.iter() {
+        let points = match digit.field {
+            ScoreField::Player => game.score().points(),
+            ScoreField::High if current.is_attract() => high_scores.best(),
+            ScoreField::High => high_scores.best().max(game.score().points()),
+        };
+        let digits = points.to_string();
+        draw.is_visible = digit.place < (digits.len() as u32).max(2);
+        let value = points / 10u32.pow(digit.place) % 10;
+        let ch = std::char::from_digit(value, 10).unwrap();
+        atlas_sprite.index = glyph_index(ch, TextColor::White);
+    }
+}
+
+fn blink_one_up(
+    game: Res<Game>,
+    mut query: Query<(&OneUp, &mut Draw)>,
+) {
+    let lit = game.is_game_over() || (game.ticks() / ONE_UP_BLINK_TICKS) % 2 == 0;
+    for (_, mut draw) in &mut query.iter() {
+        draw.is_visible = lit;
+    }
+}
+
+fn show_lives(
+    game: Res<Game>,
+    mut query: Query<(&LifeIcon, &mut Draw)>,
+) {
+    let spare = game.lives().spare();
+    for (icon, mut draw) in &mut query.iter() {
+        draw.is_visible = icon.0 < spare;
+    }
+}
+
+// The current level's fruit and those before it, oldest on the right.
+fn show_fruit_history(
+    game: Res<Game>,
+    mut query: Query<(&FruitSlot, &mut TextureAtlasSprite, &mut Draw)>,
+) {
+    let mut fruits: Vec<_> = game.level().fruit_history(FRUIT_HISTORY).collect();
+    fruits.reverse();
+    for (slot, mut atlas_sprite, mut draw) in &mut query.iter() {
+        match fruits.get(slot.0) {
+            Some(fruit) => {
+                atlas_sprite.index = fruit.texture_index();
+                draw.is_visible = true;
+            },
+            None => draw.is_visible = false,
+        }
+    }
+}
@@ -1,17 +1,52 @@
 use bevy::prelude::*;
 
+use bonus::BonusPlugin;
+use cutscene::CutscenePlugin;
 use map::MapPlugin;
 use man::ManPlugin;
+use ghost::GhostPlugin;
+use hud::HudPlugin;
+use popup::PopupPlugin;
+use scoreboard::ScoreboardPlugin;
+use sound::SoundPlugin;
 use debug::DebugPlugin;
 use sprite::SpritePlugin;
+use state::StatePlugin;
+use tick::TickPlugin;
+use title::TitlePlugin;
 
 fn main() {
-    App::build()
+    // `rpm --headless [games]` plays without a window, for soak testing.
+    let mut args = std::env::args().skip(1);
+    let flag = args.next();
+    if flag.as_deref() == Some("--headless") {
+        let games = args.next().map_or(1, |n| n.parse().expect("games should be a number"));
+        headless::run(games);
+        return;
+    }
+
+    let mut app = App::build();
+    app
         .add_default_plugins()
         .add_startup_system(global_setup.system())
+        .add_plugin(StatePlugin)
+        .add_plugin(TickPlugin)
         .add_plugin(MapPlugin)
-//        .add_plugin(DebugPlugin)
+    ;
+    // `rpm --debug` draws the tile grid and the ghosts' mode over the game.
+    if flag.as_deref() == Some("--debug") {
+        app.add_plugin(DebugPlugin);
+    }
+    app
         .add_plugin(ManPlugin)
+        .add_plugin(GhostPlugin)
+        .add_plugin(BonusPlugin)
+        .add_plugin(HudPlugin)
+        .add_plugin(ScoreboardPlugin)
+        .add_plugin(TitlePlugin)
+        .add_plugin(CutscenePlugin)
+        .add_plugin(PopupPlugin)
+        .add_plugin(SoundPlugin)
         .add_plugin(SpritePlugin)
         .run();
 }
@@ -26,6 +61,20 @@ fn global_setup(
 
 mod map;
 mod man;
+mod ghost;
+mod popup;
+mod bonus;
+mod cutscene;
+mod highscores;
+mod hud;
+mod scoreboard;
+mod sound;
+mod text;
 mod sprite;
 mod consts;
 mod debug;
+mod state;
+mod tick;
+mod title;
+mod game;
+mod headless;
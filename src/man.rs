@@ -1,16 +1,25 @@
 use bevy::prelude::*;
-use crate::sprite::Sprite;
 use crate::consts::{Direction, SCALE};
-use crate::map::{Map, map_tile_to_translation};
+use crate::game::{Actor, Game};
+use crate::map::tile_translation;
+use crate::state::{AppState, CurrentState, StateChanged};
+use crate::sprite::load_sprite_sheet;
 
 pub(crate) struct ManPlugin;
-pub(crate) struct PacMan;
+
+/// The direction the player is holding, sampled once per frame and fed to every tick.
+#[derive(Default)]
+pub(crate) struct Joystick {
+    pub direction: Option<Direction>,
+}
 
 impl Plugin for ManPlugin {
     fn build(&self, app: &mut AppBuilder) {
         app
+            .init_resource::<Joystick>()
             .add_startup_system(setup_man.system())
             .add_system(man_input.system())
+            .add_system(release_joystick.system())
         ;
     }
 }
@@ -18,26 +27,19 @@ impl Plugin for ManPlugin {
 fn setup_man(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
+    game: Res<Game>,
     mut textures: ResMut<Assets<Texture>>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
 ) {
     let pacman_sprite_sheet_components = {
-        let texture_handle = asset_server
-            .load_sync(
-                &mut textures,
-                "assets/sprites_trans.png",
-            ).unwrap();
-        let texture = textures.get(&texture_handle).unwrap();
-
-        let pacman_texture_atlas = TextureAtlas::from_grid(
-            texture_handle, texture.size, 14, 13);
-        let pacman_texture_atlas_handle = texture_atlases.add(
-            pacman_texture_atlas);
+        let pacman_texture_atlas_handle = load_sprite_sheet(
+            &asset_server, &mut textures, &mut texture_atlases);
+        let mut translation = tile_translation(game.sprite(Actor::PacMan).tile_info());
+        translation.set_z(1.0); // Above the dots.
         let mut sprite_sheet_components = SpriteSheetComponents {
             texture_atlas: pacman_texture_atlas_handle,
             transform: Transform::from_scale(SCALE)
-                .with_translation(map_tile_to_translation(
-                    14, 9, Some(0), None)),
+                .with_translation(translation),
             ..Default::default()
         };
         sprite_sheet_components.sprite.index = 1; // First tile, man with open mouth.
@@ -45,68 +47,41 @@ fn setup_man(
     };
     commands
         .spawn(pacman_sprite_sheet_components) // Add the man
-        .with(Sprite::new(Direction::Right, vec![0, 1, 2])) // Attach sprite infrastructure -- movement and animation
-        .with(PacMan)
-        .with(Timer::from_seconds(0.04, true))
+        .with(Actor::PacMan) // Drawn from the game's PacMan -- movement and animation
     ;
 }
 
+// Nothing held before READY! (by the player or the demo) carries over into play.
+fn release_joystick(
+    mut joystick: ResMut<Joystick>,
+    mut state_reader: Local<EventReader<StateChanged>>,
+    state_changes: Res<Events<StateChanged>>,
+) {
+    for change in state_reader.iter(&state_changes) {
+        if change.to == AppState::Ready {
+            joystick.direction = None;
+        }
+    }
+}
+
 fn man_input(
     keyboard_input: Res<Input<KeyCode>>,
-    map: Res<Map>,
-    mut query: Query<(&PacMan, &mut Sprite)>
+    current: Res<CurrentState>,
+    mut joystick: ResMut<Joystick>,
 ) {
-    // generalize these values for level, etc.
-    let speed = 240.0;
-    let mut sprite_update: Option<(Vec2, Direction, Vec<u32>)> = None;
-    if keyboard_input.pressed(KeyCode::Up) {
-        sprite_update =
-            Some((
-                (0.0, 1.0 * speed).into(),
-                Direction::Up,
-                vec![28, 29, 2]
-            ));
+    // The demo has its own driver.
+    if current.get() != AppState::Playing {
+        return;
+    }
+    joystick.direction = if keyboard_input.pressed(KeyCode::Up) {
+        Some(Direction::Up)
     } else if keyboard_input.pressed(KeyCode::Left) {
-        sprite_update =
-            Some((
-                (-1.0 * speed, 0.0).into(),
-                Direction::Left,
-                vec![14, 15, 2]
-            ));
+        Some(Direction::Left)
     } else if keyboard_input.pressed(KeyCode::Down) {
-        sprite_update =
-            Some((
-                (0.0, -1.0 * speed).into(),
-                Direction::Down,
-                vec![42, 43, 2]
-             ));
+        Some(Direction::Down)
     } else if keyboard_input.pressed(KeyCode::Right) {
-        sprite_update =
-            Some((
-                 (1.0 * speed, 0.0).into(),
-                Direction::Right,
-                vec![0, 1, 2],
-                 ));
-    }
-    if let Some((v, d, ix)) = sprite_update {
-        println!("Processing direction change...");
-        // Checking validity of update.
-        for (_pacman, mut sprite) in &mut query.iter() {
-            let (x, y) = sprite.tile_info().tile();
-            let adjacent = map.get_adjacent(x, y, d);
-            if let Some((_, new_tile, _)) = adjacent {
-                if new_tile.is_valid_path() {
-                    // Handle cornering.. calculate additional factor for velocity.
-                    // TODO -- then limit cornering when at position.
-                    // this is pacman-specific. The ghosts do not corner.
-                    sprite.update(v, d, ix.clone());
-                    println!("Okay, should have updated velocity...");
-                } else {
-                    println!("Cannot change direction: edge of map?")
-                }
-            } else {
-                println!("Cannot change direction: edge of map?")
-            }
-        }
-    }
+        Some(Direction::Right)
+    } else {
+        None
+    };
 }
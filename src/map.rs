@@ -1,255 +1,32 @@
 use bevy::prelude::*;
-use smallvec::SmallVec;
-use ndarray::{Array, Array2};
 
-use crate::consts::{BOUNDING_BOX, SCALE, TILE_SIZE, Direction, TILES_WIDE, TILES_HIGH};
+use crate::consts::{BOUNDING_BOX, SCALE, TILE_SIZE, TILES_WIDE, TILES_HIGH};
+use crate::game::Game;
+use crate::game::map::{DotKind, TileInformation};
+use crate::sprite::load_sprite_sheet;
 
-const I: bool = false;
-const X: bool = true;
+// Indexes into the 14x13 sprites_trans.png grid.
+pub(crate) const DOT_SPRITE_INDEX: u32 = 155;
+pub(crate) const ENERGIZER_SPRITE_INDEX: u32 = 154;
 
-#[derive(Copy, Clone, Debug, PartialEq, Eq)]
-pub(crate) struct TileInformation(u32, u32, u32, u32);
+/// Marks the all-white copy of the maze shown as it flashes at the end of a level.
+pub(crate) struct MazeFlash;
 
-impl TileInformation {
-    pub fn new() -> Self {
-        Self(0, 0, 0, 0)
-    }
-
-    pub fn x(&self) -> u32 {
-        self.0
-    }
-
-    pub fn y(&self) -> u32 {
-        self.1
-    }
-
-    pub fn x_offset(&self) -> u32 {
-        self.2
-    }
-
-    pub fn y_offset(&self) -> u32 {
-        self.3
-    }
-
-    pub fn tile(&self) -> (u32, u32) {
-        (self.x(), self.y())
-    }
-
-    pub fn past_center(&self, facing: Direction) -> bool {
-        let center = TILE_SIZE as u32 / 2;
-        match facing {
-            Direction::Up => {
-                self.y_offset() >= center
-            },
-            Direction::Right => {
-                self.x_offset() >= center
-            },
-            Direction::Down => {
-                self.y_offset() < center
-            },
-            Direction::Left => {
-                self.x_offset() < center
-            },
-        }
-    }
-
-    pub fn edge(&self) -> Option<Direction> {
-        if self.x() == (TILES_WIDE - 1) as u32 {
-            Some(Direction::Right)
-        } else if self.x() == 0 {
-            Some(Direction::Left)
-        } else if self.y() == (TILES_HIGH - 1) as u32 {
-            Some(Direction::Up)
-        } else if self.y() == 0 {
-            Some(Direction::Down)
-        } else {
-            None
-        }
-    }
-
-    pub fn align(&self, facing: Direction) -> Option<Direction> {
-        let center = TILE_SIZE as u32 / 2;
-        match facing {
-            Direction::Up | Direction::Down => {
-                if self.x_offset() == center {
-                    None
-                } else if self.x_offset() > center {
-                    Some(Direction::Left)
-                } else {
-                    Some(Direction::Right)
-                }
-            },
-            Direction::Left | Direction::Right => {
-                if self.y_offset() == center {
-                    None
-                } else if self.y_offset() > center {
-                    Some(Direction::Down)
-                } else {
-                    Some(Direction::Up)
-                }
-            },
-        }
-    }
-}
-
-impl From<(u32, u32, u32, u32)> for TileInformation {
-    fn from(inp: (u32, u32, u32, u32)) -> Self {
-        Self(inp.0, inp.1, inp.2, inp.3)
-    }
-}
-
-impl Default for TileInformation {
-    fn default() -> Self {
-        Self(0, 0, 0, 0)
-    }
-}
-
-static MAP_PATH_VALIDITY: &[bool] = &[
-    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
-    I, I, I, X, X, X, X, X, X, X, X, X, X, X, X, I, I, X, X, X, X, X, X, X, X, X, X, X, X, I, I, I,
-    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
-    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
-    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
-    I, I, I, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, I, I, I,
-    I, I, I, X, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, X, I, I, I,
-    I, I, I, X, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, X, I, I, I,
-    I, I, I, X, X, X, X, X, X, I, I, X, X, X, X, I, I, X, X, X, X, I, I, X, X, X, X, X, X, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, X, X, X, X, X, X, X, X, X, X, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
-    X, X, X, X, X, X, X, X, X, X, X, X, I, I, I, I, I, I, I, I, X, X, X, X, X, X, X, X, X, X, X, X,
-    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, X, X, X, X, X, X, X, X, X, X, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I,
-    I, I, I, X, X, X, X, X, X, X, X, X, X, X, X, I, I, X, X, X, X, X, X, X, X, X, X, X, X, I, I, I,
-    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
-    I, I, I, X, I, I, I, I, X, I, I, I, I, I, X, I, I, X, I, I, I, I, I, X, I, I, I, I, X, I, I, I,
-    I, I, I, X, X, X, I, I, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, I, I, X, X, X, I, I, I,
-    I, I, I, I, I, X, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, X, I, I, I, I, I,
-    I, I, I, I, I, X, I, I, X, I, I, X, I, I, I, I, I, I, I, I, X, I, I, X, I, I, X, I, I, I, I, I,
-    I, I, I, X, X, X, X, X, X, I, I, X, X, X, X, I, I, X, X, X, X, I, I, X, X, X, X, X, X, I, I, I,
-    I, I, I, X, I, I, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, I, I, X, I, I, I,
-    I, I, I, X, I, I, I, I, I, I, I, I, I, I, X, I, I, X, I, I, I, I, I, I, I, I, I, I, X, I, I, I,
-    I, I, I, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, X, I, I, I,
-    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
-    I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I, I,
-];
-
-pub(crate) struct Map {
-    tiles: Array2<Tile>,
-}
-
-impl Map {
-    pub fn get_tile(&self, x: u32, y:u32) -> (Tile, Vec3) {
-        self.try_tile(x, y).expect("bad tile coords?")
-    }
-
-    pub fn try_tile(&self, x: u32, y:u32) -> Option<(Tile, Vec3)> {
-        self.tiles
-            .get((x as usize, y as usize)).map(|tile| {
-            let translation = map_tile_to_translation(x, y, None, None);
-            (tile.clone(), translation)
-        })
-    }
-
-    pub fn get_adjacent(&self,
-                        mut x: u32,
-                        mut y: u32,
-                        direction: Direction) -> Option<((u32, u32), Tile, Vec3)> {
-        match direction {
-            Direction::Up => {
-                if y == (TILES_HIGH - 1) as u32 {
-                    return None;
-                } else {
-                    y += 1;
-                }
-            },
-            Direction::Right => {
-                if x == (TILES_WIDE - 1) as u32 {
-                    return None;
-                } else {
-                    x += 1;
-                }
-            },
-            Direction::Down => {
-                if y == 0 {
-                    return None;
-                } else {
-                    y -= 1;
-                }
-            },
-            Direction::Left => {
-                if x == 0 {
-                    return None;
-                } else {
-                    x -= 1;
-                }
-            },
-        }
-        let (tile, translation) = self.get_tile(x, y);
-        Some(((x, y), tile, translation))
-    }
-}
-
-impl Default for Map {
-    fn default() -> Self {
-        assert_eq!(MAP_PATH_VALIDITY.len(), TILES_WIDE * TILES_HIGH);
-        Self {
-            tiles: Array::from_shape_fn((TILES_WIDE, TILES_HIGH),
-                                        |(i, j)|  {
-                                            let idx = (((TILES_HIGH - 1) - j) * TILES_WIDE)+i;
-                                            if MAP_PATH_VALIDITY[idx] {
-                                                Tile::Path(PathTile::default())
-                                            } else {
-                                                Tile::Invalid
-                                            }
-                                        })
-        }
-    }
-}
-
-#[derive(Clone)]
-pub(crate) enum Tile {
-    Invalid,
-    Path(PathTile),
-}
-
-impl Tile {
-    pub fn is_valid_path(&self) -> bool {
-        match self {
-            &Tile::Path(_) => true,
-            _ => false,
-        }
-    }
-}
-
-#[derive(Clone)]
-pub(crate) enum TileActor {
-    Ghost,
-    Bonus,
-}
-
-#[derive(Default, Clone)]
-pub(crate) struct PathTile {
-    contents: SmallVec<[TileActor; 8]>,
+/// Marks the sprite drawn for the dot or energizer on a tile.
+pub(crate) struct DotSprite {
+    pub tile: (u32, u32),
+    pub kind: DotKind,
 }
 
 pub(crate) struct MapPlugin;
 
 impl Plugin for MapPlugin {
     fn build(&self, app: &mut AppBuilder) {
-        let map = Map::default();
         app
-            .add_resource(map)
             .add_startup_system(setup_map.system())
+            .add_startup_system(setup_dots.system())
+            .add_system(show_dots.system())
+            .add_system(flash_maze.system())
         ;
     }
 }
@@ -257,16 +34,15 @@ impl Plugin for MapPlugin {
 fn setup_map(
     mut commands: Commands,
     asset_server: Res<AssetServer>,
-    map: Res<Map>,
     mut materials: ResMut<Assets<ColorMaterial>>,
     mut textures: ResMut<Assets<Texture>>,
     mut texture_atlases: ResMut<Assets<TextureAtlas>>,
 ) {
-    let map_sprite_sheet_components = {
+    let mut maze = |path: &str, z: f32| {
         let texture_handle = asset_server
             .load_sync(
                 &mut textures,
-                "assets/map.png",
+                path,
             ).unwrap();
         let texture = textures.get(&texture_handle).unwrap();
 
@@ -274,14 +50,16 @@ fn setup_map(
         let map_texture_atlas = TextureAtlas::from_grid(
             texture_handle, texture.size, 1, 1);
         let map_texture_atlas_handle = texture_atlases.add(map_texture_atlas);
-        let sprite_sheet_components = SpriteSheetComponents {
+        SpriteSheetComponents {
             texture_atlas: map_texture_atlas_handle,
             transform: Transform::from_scale(SCALE)
-                .with_translation(Vec3::new(0.0, -4.0 * SCALE, 0.0)),
+                .with_translation(Vec3::new(0.0, -4.0 * SCALE, z)),
             ..Default::default()
-        };
-        sprite_sheet_components
+        }
     };
+    let map_sprite_sheet_components = maze("assets/map.png", 0.0);
+    let mut flash_sprite_sheet_components = maze("assets/map_white.png", 0.1);
+    flash_sprite_sheet_components.draw.is_visible = false;
     commands
         .spawn(SpriteComponents {
             material: materials.add(Color::rgb(0.0, 0.0, 0.0).into()),
@@ -292,6 +70,8 @@ fn setup_map(
             ..Default::default()
         })
         .spawn(map_sprite_sheet_components) // Add the map.
+        .spawn(flash_sprite_sheet_components)
+        .with(MazeFlash)
     ;
 
     // Now, let's hide the tunnel under black squares.
@@ -316,6 +96,63 @@ fn setup_map(
         });
 }
 
+fn setup_dots(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game: Res<Game>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let dot_texture_atlas_handle = load_sprite_sheet(
+        &asset_server, &mut textures, &mut texture_atlases);
+
+    for x in 0..TILES_WIDE {
+        for y in 0..TILES_HIGH {
+            let (x, y) = (x as u32, y as u32);
+            if let Some(kind) = game.map().dot(x, y) {
+                let mut translation = map_tile_to_translation(x, y, None, None);
+                // Above the maze, below the actors.
+                translation.set_z(0.5);
+                let mut sprite_sheet_components = SpriteSheetComponents {
+                    texture_atlas: dot_texture_atlas_handle,
+                    transform: Transform::from_scale(SCALE)
+                        .with_translation(translation),
+                    ..Default::default()
+                };
+                sprite_sheet_components.sprite.index = match kind {
+                    DotKind::Dot => DOT_SPRITE_INDEX,
+                    DotKind::Energizer => ENERGIZER_SPRITE_INDEX,
+                };
+                commands
+                    .spawn(sprite_sheet_components)
+                    .with(DotSprite { tile: (x, y), kind })
+                ;
+            }
+        }
+    }
+}
+
+// Dots stay put for the whole game; they're just hidden once eaten and shown again when
+// the board is reset for the next level.
+fn show_dots(
+    game: Res<Game>,
+    mut query: Query<(&DotSprite, &mut Draw)>,
+) {
+    for (dot_sprite, mut draw) in &mut query.iter() {
+        let (x, y) = dot_sprite.tile;
+        draw.is_visible = game.map().dot(x, y).is_some();
+    }
+}
+
+fn flash_maze(
+    game: Res<Game>,
+    mut query: Query<(&MazeFlash, &mut Draw)>,
+) {
+    for (_, mut draw) in &mut query.iter() {
+        draw.is_visible = game.is_maze_white();
+    }
+}
+
 pub(crate) fn map_tile_to_translation(
     x_tile: u32,
     y_tile: u32,
@@ -344,17 +181,7 @@ pub(crate) fn map_tile_to_translation(
     res
 }
 
-pub(crate) fn translation_to_map_tile(
-    translation: &Vec4,
-) -> TileInformation {
-
-    let x = (translation.x() / SCALE) + BOUNDING_BOX.right;
-    let x_tile = x as u32 / TILE_SIZE as u32;
-    let x_offset = x as u32 % TILE_SIZE as u32;
-
-    let y = (translation.y() / SCALE) + BOUNDING_BOX.top;
-    let y_tile = y as u32 / TILE_SIZE as u32;
-    let y_offset = y as u32 % TILE_SIZE as u32;
-
-    (x_tile, y_tile, x_offset, y_offset).into()
+pub(crate) fn tile_translation(tile_info: TileInformation) -> Vec3 {
+    map_tile_to_translation(
+        tile_info.x(), tile_info.y(), Some(tile_info.x_offset()), Some(tile_info.y_offset()))
 }
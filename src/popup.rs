@@ -0,0 +1,151 @@
+use bevy::prelude::*;
+use crate::consts::{SCALE, TILE_SIZE};
+use crate::game::bonus::{BONUS_POSITION, Fruit};
+use crate::game::map::TileInformation;
+use crate::game::events::Event;
+use crate::map::{map_tile_to_translation, tile_translation};
+use crate::sprite::load_sprite_sheet;
+
+// How long point values stay on screen after eating a ghost or the fruit.
+const GHOST_POPUP_SECONDS: f32 = 1.0;
+const FRUIT_POPUP_SECONDS: f32 = 2.0;
+
+/// A point value floating over the maze, removed when its timer runs out.
+pub(crate) struct Popup {
+    timer: Timer,
+}
+
+// The sprite sheet, kept around so popups can be spawned at any time.
+struct PopupSheet(Handle<TextureAtlas>);
+
+pub(crate) struct PopupPlugin;
+
+impl Plugin for PopupPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .add_startup_system(setup_popups.system())
+            .add_system(spawn_ghost_popups.system())
+            .add_system(spawn_fruit_popups.system())
+            .add_system(expire_popups.system())
+        ;
+    }
+}
+
+fn setup_popups(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let sheet = load_sprite_sheet(&asset_server, &mut textures, &mut texture_atlases);
+    commands.insert_resource(PopupSheet(sheet));
+}
+
+/// Show a sprite from the sheet at `translation` for `seconds`.
+pub(crate) fn spawn_popup(
+    commands: &mut Commands,
+    sheet: Handle<TextureAtlas>,
+    index: u32,
+    mut translation: Vec3,
+    seconds: f32,
+) {
+    translation.set_z(3.0);
+    let mut sprite_sheet_components = SpriteSheetComponents {
+        texture_atlas: sheet,
+        transform: Transform::from_scale(SCALE)
+            .with_translation(translation),
+        ..Default::default()
+    };
+    sprite_sheet_components.sprite.index = index;
+    commands
+        .spawn(sprite_sheet_components)
+        .with(Popup { timer: Timer::from_seconds(seconds, false) })
+    ;
+}
+
+fn spawn_ghost_popups(
+    mut commands: Commands,
+    sheet: Res<PopupSheet>,
+    mut event_reader: Local<EventReader<Event>>,
+    events: Res<Events<Event>>,
+) {
+    for event in event_reader.iter(&events) {
+        let ghost_eaten = match event {
+            Event::GhostEaten(ghost_eaten) => ghost_eaten,
+            _ => continue,
+        };
+        let (x, y) = ghost_eaten.tile;
+        spawn_popup(
+            &mut commands,
+            sheet.0,
+            ghost_score_index(ghost_eaten.points),
+            map_tile_to_translation(x, y, None, None),
+            GHOST_POPUP_SECONDS,
+        );
+    }
+}
+
+fn spawn_fruit_popups(
+    mut commands: Commands,
+    sheet: Res<PopupSheet>,
+    mut event_reader: Local<EventReader<Event>>,
+    events: Res<Events<Event>>,
+) {
+    for event in event_reader.iter(&events) {
+        let bonus_eaten = match event {
+            Event::BonusEaten(bonus_eaten) => bonus_eaten,
+            _ => continue,
+        };
+        // The bigger values are drawn across several cells, so lay them out side by side
+        // around where the fruit was.
+        let indexes = fruit_score_indexes(bonus_eaten.fruit);
+        let center = tile_translation(TileInformation::from(BONUS_POSITION));
+        for (i, &index) in indexes.iter().enumerate() {
+            let offset = (i as f32 - (indexes.len() - 1) as f32 / 2.0) * 2.0 * TILE_SIZE * SCALE;
+            spawn_popup(
+                &mut commands,
+                sheet.0,
+                index,
+                center + Vec3::new(offset, 0.0, 0.0),
+                FRUIT_POPUP_SECONDS,
+            );
+        }
+    }
+}
+
+fn expire_popups(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Popup)>,
+) {
+    for (entity, mut popup) in &mut query.iter() {
+        popup.timer.tick(time.delta_seconds);
+        if popup.timer.finished {
+            commands.despawn(entity);
+        }
+    }
+}
+
+// Ghost point values sit in a row in the sprite sheet: 200, 400, 800, 1600.
+fn ghost_score_index(points: u32) -> u32 {
+    match points {
+        200 => 112,
+        400 => 113,
+        800 => 114,
+        _ => 115,
+    }
+}
+
+// Fruit point values, some of which are wider than one cell.
+fn fruit_score_indexes(fruit: Fruit) -> &'static [u32] {
+    match fruit {
+        Fruit::Cherries => &[126],
+        Fruit::Strawberry => &[127],
+        Fruit::Peach => &[128],
+        Fruit::Apple => &[129],
+        Fruit::Melon => &[130, 131],
+        Fruit::Galaxian => &[143, 144, 145],
+        Fruit::Bell => &[157, 158, 159],
+        Fruit::Key => &[171, 172, 173],
+    }
+}
@@ -0,0 +1,261 @@
+use bevy::prelude::*;
+
+use crate::consts::{SCALE, TILE_SIZE, TILES_WIDE};
+use crate::game::Game;
+use crate::highscores::{HighScores, INITIALS};
+use crate::map::map_tile_to_translation;
+use crate::state::{AppState, StateChanged};
+use crate::text::{TextColor, glyph_index, load_font, spawn_text};
+
+// The scoreboard covers the maze but leaves the scores showing along the top.
+const BACKDROP_Z: f32 = 4.0;
+const TEXT_Z: f32 = 5.0;
+const BACKDROP_TILES_HIGH: u32 = 34;
+
+const PROMPT_TILE: (u32, u32) = (7, 24);
+const INITIALS_TILE: (u32, u32) = (15, 20);
+
+const TITLE_TILE: (u32, u32) = (11, 30);
+const TABLE_LEFT_TILE: u32 = 7;
+const TABLE_TOP_TILE: u32 = 27;
+
+/// Drawn under the letter being picked.
+const CURSOR_GLYPH: char = '-';
+
+/// The high score table on the title screen, with initials entry first if the game just
+/// played made it onto the table.
+pub(crate) struct ScoreboardPlugin;
+
+// The font and a plain black backdrop, kept around for when the game ends.
+struct ScoreboardAssets {
+    font: Handle<TextureAtlas>,
+    backdrop: Handle<ColorMaterial>,
+}
+
+/// Initials being entered for a new high score.
+#[derive(Default)]
+pub(crate) struct InitialsEntry {
+    entering: Option<Entering>,
+}
+
+struct Entering {
+    points: u32,
+    letters: [u8; INITIALS],
+    cursor: usize,
+}
+
+impl InitialsEntry {
+    pub fn is_entering(&self) -> bool {
+        self.entering.is_some()
+    }
+}
+
+/// Marks everything on the scoreboard, to clear it away again.
+#[derive(Clone)]
+struct ScoreboardScreen;
+
+/// One of the initials being entered.
+struct InitialSlot(usize);
+
+/// Marks the glyph under the letter being picked.
+struct InitialsCursor;
+
+impl Plugin for ScoreboardPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .add_resource(HighScores::load())
+            .init_resource::<InitialsEntry>()
+            .add_startup_system(setup_scoreboard.system())
+            .add_system(open_scoreboard.system())
+            .add_system(enter_initials.system())
+            .add_system(show_initials.system())
+        ;
+    }
+}
+
+fn setup_scoreboard(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let font = load_font(&asset_server, &mut textures, &mut texture_atlases);
+    let backdrop = materials.add(Color::rgb(0.0, 0.0, 0.0).into());
+    commands.insert_resource(ScoreboardAssets { font, backdrop });
+}
+
+fn open_scoreboard(
+    mut commands: Commands,
+    assets: Res<ScoreboardAssets>,
+    game: Res<Game>,
+    high_scores: Res<HighScores>,
+    mut entry: ResMut<InitialsEntry>,
+    mut state_reader: Local<EventReader<StateChanged>>,
+    state_changes: Res<Events<StateChanged>>,
+    mut query: Query<(Entity, &ScoreboardScreen)>,
+) {
+    for change in state_reader.iter(&state_changes) {
+        if change.from == AppState::Title {
+            for (entity, _) in &mut query.iter() {
+                commands.despawn(entity);
+            }
+        }
+        if change.to != AppState::Title {
+            continue;
+        }
+        let points = game.score().points();
+        spawn_backdrop(&mut commands, &assets);
+        if change.from == AppState::GameOver && high_scores.qualifies(points) {
+            entry.entering = Some(Entering {
+                points,
+                letters: [b'A'; INITIALS],
+                cursor: 0,
+            });
+            spawn_entry(&mut commands, &assets);
+        } else {
+            spawn_table(&mut commands, &assets, &high_scores);
+        }
+    }
+}
+
+// Up and down run through the alphabet, right (or enter) moves on to the next letter and
+// left goes back. Confirming the last letter saves the score.
+fn enter_initials(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    assets: Res<ScoreboardAssets>,
+    mut high_scores: ResMut<HighScores>,
+    mut entry: ResMut<InitialsEntry>,
+    mut query: Query<(Entity, &ScoreboardScreen)>,
+) {
+    let entering = match entry.entering.as_mut() {
+        Some(entering) => entering,
+        None => return,
+    };
+    let letter = &mut entering.letters[entering.cursor];
+    if keyboard_input.just_pressed(KeyCode::Up) {
+        *letter = if *letter == b'Z' { b'A' } else { *letter + 1 };
+    } else if keyboard_input.just_pressed(KeyCode::Down) {
+        *letter = if *letter == b'A' { b'Z' } else { *letter - 1 };
+    } else if keyboard_input.just_pressed(KeyCode::Left)
+        || keyboard_input.just_pressed(KeyCode::Back) {
+        entering.cursor = entering.cursor.saturating_sub(1);
+    } else if keyboard_input.just_pressed(KeyCode::Right)
+        || keyboard_input.just_pressed(KeyCode::Return) {
+        entering.cursor += 1;
+    }
+    if entering.cursor < INITIALS {
+        return;
+    }
+
+    let initials = entering.letters.iter().map(|&letter| letter as char).collect();
+    high_scores.insert(initials, entering.points);
+    if let Err(err) = high_scores.save() {
+        eprintln!("Couldn't save high scores: {}", err);
+    }
+    entry.entering = None;
+    for (entity, _) in &mut query.iter() {
+        commands.despawn(entity);
+    }
+    spawn_backdrop(&mut commands, &assets);
+    spawn_table(&mut commands, &assets, &high_scores);
+}
+
+fn show_initials(
+    entry: Res<InitialsEntry>,
+    mut letters: Query<(&InitialSlot, &mut TextureAtlasSprite)>,
+    mut cursors: Query<(&InitialsCursor, &mut Transform)>,
+) {
+    let entering = match entry.entering.as_ref() {
+        Some(entering) => entering,
+        None => return,
+    };
+    for (slot, mut atlas_sprite) in &mut letters.iter() {
+        atlas_sprite.index = glyph_index(entering.letters[slot.0] as char, TextColor::White);
+    }
+    let (x, y) = INITIALS_TILE;
+    let cursor = map_tile_to_translation(x + entering.cursor as u32, y - 1, None, None);
+    for (_, mut transform) in &mut cursors.iter() {
+        let translation = transform.translation_mut();
+        translation.set_x(cursor.x());
+    }
+}
+
+fn spawn_backdrop(commands: &mut Commands, assets: &ScoreboardAssets) {
+    let center = map_tile_to_translation(
+        TILES_WIDE as u32 / 2, BACKDROP_TILES_HIGH / 2, Some(0), Some(0));
+    commands
+        .spawn(SpriteComponents {
+            material: assets.backdrop,
+            transform: Transform::from_translation(
+                Vec3::new(center.x(), center.y(), BACKDROP_Z)),
+            sprite: Sprite::new(Vec2::new(TILES_WIDE as f32 * TILE_SIZE * SCALE,
+                                          BACKDROP_TILES_HIGH as f32 * TILE_SIZE * SCALE)),
+            ..Default::default()
+        })
+        .with(ScoreboardScreen)
+    ;
+}
+
+fn spawn_entry(commands: &mut Commands, assets: &ScoreboardAssets) {
+    let font = assets.font;
+    spawn_text(
+        commands, font, "ENTER YOUR INITIALS", TextColor::White,
+        PROMPT_TILE, TEXT_Z, ScoreboardScreen);
+    let (x, y) = INITIALS_TILE;
+    for slot in 0..INITIALS {
+        let mut translation = map_tile_to_translation(x + slot as u32, y, None, None);
+        translation.set_z(TEXT_Z);
+        commands
+            .spawn(SpriteSheetComponents {
+                texture_atlas: font,
+                transform: Transform::from_scale(SCALE)
+                    .with_translation(translation),
+                ..Default::default()
+            })
+            .with(ScoreboardScreen)
+            .with(InitialSlot(slot))
+        ;
+    }
+    let mut translation = map_tile_to_translation(x, y - 1, None, None);
+    translation.set_z(TEXT_Z);
+    let mut cursor = SpriteSheetComponents {
+        texture_atlas: font,
+        transform: Transform::from_scale(SCALE)
+            .with_translation(translation),
+        ..Default::default()
+    };
+    cursor.sprite.index = glyph_index(CURSOR_GLYPH, TextColor::White);
+    commands
+        .spawn(cursor)
+        .with(ScoreboardScreen)
+        .with(InitialsCursor)
+    ;
+}
+
+// Rank, score right-aligned, then initials: " 1ST   12340  ABC".
+fn spawn_table(commands: &mut Commands, assets: &ScoreboardAssets, high_scores: &HighScores) {
+    let font = assets.font;
+    spawn_text(
+        commands, font, "HIGH SCORES", TextColor::White,
+        TITLE_TILE, TEXT_Z, ScoreboardScreen);
+    for (i, entry) in high_scores.entries().iter().enumerate() {
+        let rank = i + 1;
+        let line = format!(
+            "{:>2}{}  {:>7}  {}", rank, ordinal_suffix(rank), entry.points, entry.initials);
+        let y = TABLE_TOP_TILE - 2 * i as u32;
+        spawn_text(
+            commands, font, &line, TextColor::White,
+            (TABLE_LEFT_TILE, y), TEXT_Z, ScoreboardScreen);
+    }
+}
+
+fn ordinal_suffix(rank: usize) -> &'static str {
+    match rank {
+        1 => "ST",
+        2 => "ND",
+        3 => "RD",
+        _ => "TH",
+    }
+}
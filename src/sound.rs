@@ -0,0 +1,153 @@
+use bevy::prelude::*;
+
+use crate::game::Game;
+use crate::game::events::Event;
+use crate::state::{AppState, CurrentState};
+
+// The siren climbs a step each time the dots left on the board drop below one of these.
+const SIREN_STEPS: [usize; 3] = [180, 120, 60];
+
+// Bevy can only start a sound, not loop or stop one, so the background sounds are short
+// clips started again each time they run out. These are the clips' lengths.
+const SIREN_SECONDS: [f32; 4] = [0.40, 0.36, 0.32, 0.28];
+const FRIGHT_SECONDS: f32 = 0.26;
+const EYES_SECONDS: f32 = 0.20;
+
+/// Sound effects, all driven by what the game reports. The attract mode is silent.
+pub(crate) struct SoundPlugin;
+
+/// The background sound while the game is being played; only one plays at a time.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum Loop {
+    // Which step of the siren, from 0.
+    Siren(usize),
+    // The ghosts are blue.
+    Fright,
+    // A ghost that was eaten is on its way home.
+    Eyes,
+}
+
+struct Sounds {
+    sirens: [Handle<AudioSource>; 4],
+    fright: Handle<AudioSource>,
+    eyes: Handle<AudioSource>,
+    // Alternated, one per dot.
+    wakas: [Handle<AudioSource>; 2],
+    ghost_eaten: Handle<AudioSource>,
+    death: Handle<AudioSource>,
+    extra_life: Handle<AudioSource>,
+}
+
+impl Sounds {
+    fn looping(&self, looping: Loop) -> (Handle<AudioSource>, f32) {
+        match looping {
+            Loop::Siren(step) => (self.sirens[step], SIREN_SECONDS[step]),
+            Loop::Fright => (self.fright, FRIGHT_SECONDS),
+            Loop::Eyes => (self.eyes, EYES_SECONDS),
+        }
+    }
+}
+
+/// What's playing: the current loop and when it next needs starting again.
+#[derive(Default)]
+struct Soundtrack {
+    looping: Option<(Loop, Timer)>,
+    next_waka: usize,
+}
+
+impl Plugin for SoundPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .init_resource::<Soundtrack>()
+            .add_startup_system(setup_sounds.system())
+            .add_system(play_effects.system())
+            .add_system(play_loops.system())
+        ;
+    }
+}
+
+fn setup_sounds(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+) {
+    let load = |name: &str| {
+        asset_server.load(format!("assets/sounds/{}.wav", name)).unwrap()
+    };
+    commands.insert_resource(Sounds {
+        sirens: [load("siren_1"), load("siren_2"), load("siren_3"), load("siren_4")],
+        fright: load("fright"),
+        eyes: load("eyes"),
+        wakas: [load("waka_1"), load("waka_2")],
+        ghost_eaten: load("ghost_eaten"),
+        death: load("death"),
+        extra_life: load("extra_life"),
+    });
+}
+
+// One-off sounds for things that just happened.
+fn play_effects(
+    sounds: Res<Sounds>,
+    audio_output: Res<AudioOutput>,
+    current: Res<CurrentState>,
+    mut soundtrack: ResMut<Soundtrack>,
+    mut event_reader: Local<EventReader<Event>>,
+    events: Res<Events<Event>>,
+) {
+    for event in event_reader.iter(&events) {
+        if current.is_attract() {
+            continue;
+        }
+        let sound = match event {
+            Event::DotEaten(_) => {
+                let waka = soundtrack.next_waka;
+                soundtrack.next_waka = (waka + 1) % sounds.wakas.len();
+                sounds.wakas[waka]
+            },
+            Event::GhostEaten(_) => sounds.ghost_eaten,
+            Event::PacManDying(_) => sounds.death,
+            Event::ExtraLife(_) => sounds.extra_life,
+            _ => continue,
+        };
+        audio_output.play(sound);
+    }
+}
+
+// The siren, or whatever has taken its place, runs for as long as PacMan is free to move.
+// Eyes drown out the energizer siren, which drowns out the normal one.
+fn play_loops(
+    time: Res<Time>,
+    game: Res<Game>,
+    sounds: Res<Sounds>,
+    audio_output: Res<AudioOutput>,
+    current: Res<CurrentState>,
+    mut soundtrack: ResMut<Soundtrack>,
+) {
+    let wanted = if current.get() != AppState::Playing || game.is_frozen() {
+        None
+    } else if game.ghosts().iter().any(|ghost| ghost.is_eyes()) {
+        Some(Loop::Eyes)
+    } else if game.fright_timer().is_some() {
+        Some(Loop::Fright)
+    } else {
+        let dots_left = game.map().dots_remaining();
+        Some(Loop::Siren(SIREN_STEPS.iter().filter(|&&step| dots_left < step).count()))
+    };
+
+    let restart = match (soundtrack.looping.as_mut(), wanted) {
+        (Some((looping, timer)), Some(wanted)) if *looping == wanted => {
+            timer.tick(time.delta_seconds);
+            timer.finished
+        },
+        (_, wanted) => wanted.is_some(),
+    };
+    soundtrack.looping = match wanted {
+        Some(wanted) if restart => {
+            let (sound, seconds) = sounds.looping(wanted);
+            audio_output.play(sound);
+            Some((wanted, Timer::from_seconds(seconds, false)))
+        },
+        Some(_) => soundtrack.looping.take(),
+        // Whatever's left of the last clip just plays out.
+        None => None,
+    };
+}
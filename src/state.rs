@@ -0,0 +1,241 @@
+use bevy::prelude::*;
+
+use crate::game::Game;
+use crate::game::events::Event;
+use crate::scoreboard::InitialsEntry;
+use crate::text::{TextColor, load_font, spawn_text};
+
+// The attract loop: the high score table, the cast of characters, then a demo game.
+const TITLE_SECONDS: f32 = 5.0;
+const INTRO_SECONDS: f32 = 12.0;
+
+// The first READY! of a game is held longer, as the arcade plays its intro tune over it.
+const FIRST_READY_SECONDS: f32 = 4.0;
+const READY_SECONDS: f32 = 2.0;
+const GAME_OVER_SECONDS: f32 = 3.0;
+
+// READY! and GAME OVER share the row just below the ghost house; the start prompt sits
+// under the high score table. All of it goes over the scoreboard.
+const READY_TILE: (u32, u32) = (14, 15);
+const GAME_OVER_TILE: (u32, u32) = (11, 15);
+const START_PROMPT_TILE: (u32, u32) = (8, 5);
+const MESSAGE_Z: f32 = 5.0;
+
+/// The phases the app moves through. The game itself only ticks during Demo, Playing, Dying,
+/// LevelComplete and Intermission; the last three play out the core's own death, maze flash
+/// and cutscene sequences.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum AppState {
+    Title,
+    Intro,
+    Demo,
+    Ready,
+    Playing,
+    Dying,
+    LevelComplete,
+    Intermission,
+    GameOver,
+}
+
+/// The current AppState, and how long until it moves on by itself, for those that do.
+pub(crate) struct CurrentState {
+    state: AppState,
+    timer: Option<Timer>,
+}
+
+impl Default for CurrentState {
+    fn default() -> Self {
+        Self {
+            state: AppState::Intro,
+            timer: Some(Timer::from_seconds(INTRO_SECONDS, false)),
+        }
+    }
+}
+
+impl CurrentState {
+    pub fn get(&self) -> AppState {
+        self.state
+    }
+
+    /// Seconds since the last transition, for states that move on by themselves.
+    pub fn elapsed(&self) -> f32 {
+        self.timer.as_ref().map_or(0.0, |timer| timer.elapsed)
+    }
+
+    /// Whether `Game::tick` should run.
+    pub fn runs_game(&self) -> bool {
+        match self.state {
+            AppState::Demo
+            | AppState::Playing
+            | AppState::Dying
+            | AppState::LevelComplete
+            | AppState::Intermission => true,
+            _ => false,
+        }
+    }
+
+    /// Whether the game reporting `event` moves the app on from the current state.
+    pub fn changes_with(&self, event: &Event) -> bool {
+        next_state(self.state, event).is_some()
+    }
+
+    /// Whether the game on screen is only the attract mode demo.
+    pub fn is_attract(&self) -> bool {
+        match self.state {
+            AppState::Title | AppState::Intro | AppState::Demo => true,
+            _ => false,
+        }
+    }
+}
+
+/// Sent on every transition, so plugins can set up and tear down whatever goes with a phase.
+#[derive(Copy, Clone, Debug)]
+pub(crate) struct StateChanged {
+    pub from: AppState,
+    pub to: AppState,
+}
+
+/// Marks the text shown only in some states.
+#[derive(Clone)]
+struct StateText(&'static [AppState]);
+
+pub(crate) struct StatePlugin;
+
+impl Plugin for StatePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .init_resource::<CurrentState>()
+            .add_event::<StateChanged>()
+            .add_startup_system(setup_state_text.system())
+            .add_system(start_game.system())
+            .add_system(follow_game.system())
+            .add_system(run_timers.system())
+            .add_system(show_state_text.system())
+        ;
+    }
+}
+
+fn setup_state_text(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+) {
+    let font = load_font(&asset_server, &mut textures, &mut texture_atlases);
+    spawn_text(
+        &mut commands, font, "READY!", TextColor::Yellow,
+        READY_TILE, MESSAGE_Z, StateText(&[AppState::Ready]));
+    // The demo plays under a standing GAME OVER, as in the arcade.
+    spawn_text(
+        &mut commands, font, "GAME  OVER", TextColor::Red,
+        GAME_OVER_TILE, MESSAGE_Z, StateText(&[AppState::GameOver, AppState::Demo]));
+    spawn_text(
+        &mut commands, font, "PUSH SPACE TO START", TextColor::Orange,
+        START_PROMPT_TILE, MESSAGE_Z, StateText(&[AppState::Title, AppState::Intro]));
+}
+
+fn transition(
+    current: &mut CurrentState,
+    state_changes: &mut Events<StateChanged>,
+    to: AppState,
+    seconds: Option<f32>,
+) {
+    let from = current.state;
+    current.state = to;
+    current.timer = seconds.map(|seconds| Timer::from_seconds(seconds, false));
+    state_changes.send(StateChanged { from, to });
+}
+
+// A fresh game from anywhere in the attract loop, once any initials have been entered.
+fn start_game(
+    keyboard_input: Res<Input<KeyCode>>,
+    entry: Res<InitialsEntry>,
+    mut game: ResMut<Game>,
+    mut current: ResMut<CurrentState>,
+    mut state_changes: ResMut<Events<StateChanged>>,
+) {
+    if !current.is_attract() || entry.is_entering() {
+        return;
+    }
+    if keyboard_input.just_pressed(KeyCode::Space) {
+        *game = Game::new();
+        transition(&mut current, &mut state_changes, AppState::Ready, Some(FIRST_READY_SECONDS));
+    }
+}
+
+// Most transitions just follow what the game reports.
+fn follow_game(
+    mut current: ResMut<CurrentState>,
+    mut state_changes: ResMut<Events<StateChanged>>,
+    mut event_reader: Local<EventReader<Event>>,
+    events: Res<Events<Event>>,
+) {
+    for event in event_reader.iter(&events) {
+        if let Some((to, seconds)) = next_state(current.state, event) {
+            transition(&mut current, &mut state_changes, to, seconds);
+        }
+    }
+}
+
+// Where the game reporting `event` takes the app from `state`, and for how long. The demo
+// runs until PacMan's first death (or, if the autopilot gets that far, the end of the level).
+fn next_state(state: AppState, event: &Event) -> Option<(AppState, Option<f32>)> {
+    let next = match (state, event) {
+        (AppState::Demo, Event::LifeLost(_))
+        | (AppState::Demo, Event::LevelComplete(_)) => (AppState::Title, Some(TITLE_SECONDS)),
+        (AppState::Demo, _) => return None,
+        (_, Event::PacManCaught(_)) => (AppState::Dying, None),
+        (_, Event::LifeLost(life_lost)) if life_lost.lives > 0 => {
+            (AppState::Ready, Some(READY_SECONDS))
+        },
+        (_, Event::GameOver(_)) => (AppState::GameOver, Some(GAME_OVER_SECONDS)),
+        (_, Event::LevelComplete(_)) => (AppState::LevelComplete, None),
+        (_, Event::IntermissionStarted(_)) => (AppState::Intermission, None),
+        (_, Event::LevelStarted(_)) => (AppState::Ready, Some(READY_SECONDS)),
+        _ => return None,
+    };
+    Some(next)
+}
+
+// The rest happen after a pause. The title screen waits for any initials to be entered.
+fn run_timers(
+    time: Res<Time>,
+    entry: Res<InitialsEntry>,
+    mut game: ResMut<Game>,
+    mut current: ResMut<CurrentState>,
+    mut state_changes: ResMut<Events<StateChanged>>,
+) {
+    if current.state == AppState::Title && entry.is_entering() {
+        return;
+    }
+    let finished = match current.timer.as_mut() {
+        Some(timer) => {
+            timer.tick(time.delta_seconds);
+            timer.finished
+        },
+        None => false,
+    };
+    if !finished {
+        return;
+    }
+    let (to, seconds) = match current.state {
+        AppState::Title => (AppState::Intro, Some(INTRO_SECONDS)),
+        AppState::Intro => {
+            *game = Game::new();
+            (AppState::Demo, None)
+        },
+        AppState::Ready => (AppState::Playing, None),
+        AppState::GameOver => (AppState::Title, Some(TITLE_SECONDS)),
+        _ => return,
+    };
+    transition(&mut current, &mut state_changes, to, seconds);
+}
+
+fn show_state_text(
+    current: Res<CurrentState>,
+    mut query: Query<(&StateText, &mut Draw)>,
+) {
+    for (text, mut draw) in &mut query.iter() {
+        draw.is_visible = text.0.contains(&current.state);
+    }
+}
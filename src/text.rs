@@ -0,0 +1,83 @@
+use bevy::prelude::*;
+use bevy::ecs::Component;
+
+use crate::consts::SCALE;
+use crate::map::map_tile_to_translation;
+
+// font.png holds the arcade's 8x8 characters from ' ' to '_' in ASCII order, 16 to a row,
+// once for each colour.
+const FONT_COLUMNS: usize = 16;
+const ROWS_PER_COLOR: usize = 4;
+const FONT_COLORS: usize = 6;
+const FONT_ROWS: usize = ROWS_PER_COLOR * FONT_COLORS;
+const FIRST_GLYPH: char = ' ';
+const LAST_GLYPH: char = '_';
+
+/// The colours the font comes in, in the order they're laid out in font.png.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub(crate) enum TextColor {
+    White,
+    Yellow,
+    Red,
+    Pink,
+    Cyan,
+    Orange,
+}
+
+/// The arcade font as an atlas of 8x8 glyphs.
+pub(crate) fn load_font(
+    asset_server: &AssetServer,
+    textures: &mut Assets<Texture>,
+    texture_atlases: &mut Assets<TextureAtlas>,
+) -> Handle<TextureAtlas> {
+    let texture_handle = asset_server
+        .load_sync(
+            textures,
+            "assets/font.png",
+        ).unwrap();
+    let texture = textures.get(&texture_handle).unwrap();
+    let texture_atlas = TextureAtlas::from_grid(
+        texture_handle, texture.size, FONT_COLUMNS, FONT_ROWS);
+    texture_atlases.add(texture_atlas)
+}
+
+/// Where a character sits in the font. Anything it doesn't have comes out as a space.
+pub(crate) fn glyph_index(ch: char, color: TextColor) -> u32 {
+    let ch = ch.to_ascii_uppercase();
+    let first = color as u32 * (ROWS_PER_COLOR * FONT_COLUMNS) as u32;
+    if ch < FIRST_GLYPH || ch > LAST_GLYPH {
+        return first;
+    }
+    first + ch as u32 - FIRST_GLYPH as u32
+}
+
+/// Write `text` one character per tile, left to right from tile (x, y) at depth `z`, tagging
+/// every character with `marker` so it can be found again.
+pub(crate) fn spawn_text(
+    commands: &mut Commands,
+    font: Handle<TextureAtlas>,
+    text: &str,
+    color: TextColor,
+    (x, y): (u32, u32),
+    z: f32,
+    marker: impl Component + Clone,
+) {
+    for (i, ch) in text.chars().enumerate() {
+        if ch == ' ' {
+            continue;
+        }
+        let mut translation = map_tile_to_translation(x + i as u32, y, None, None);
+        translation.set_z(z);
+        let mut sprite_sheet_components = SpriteSheetComponents {
+            texture_atlas: font,
+            transform: Transform::from_scale(SCALE)
+                .with_translation(translation),
+            ..Default::default()
+        };
+        sprite_sheet_components.sprite.index = glyph_index(ch, color);
+        commands
+            .spawn(sprite_sheet_components)
+            .with(marker.clone())
+        ;
+    }
+}
@@ -0,0 +1,86 @@
+use bevy::prelude::*;
+
+use crate::game::{Game, TICKS_PER_SECOND};
+use crate::game::events::Event;
+use crate::man::Joystick;
+use crate::state::CurrentState;
+
+pub(crate) const TICK_SECONDS: f32 = 1.0 / TICKS_PER_SECOND as f32;
+
+// After a long stall (window drag, breakpoint) we drop time rather than fast-forward through it.
+const MAX_TICKS_PER_FRAME: u32 = 8;
+
+/// Gameplay runs at a fixed 60Hz, independent of the render frame rate. Each frame, the
+/// elapsed time is banked and spent in whole ticks; what's left over is used to
+/// interpolate sprite positions for rendering.
+#[derive(Default)]
+pub(crate) struct FixedTick {
+    accumulator: f32,
+    pending: u32,
+}
+
+impl FixedTick {
+    /// How far we are between the last tick and the next, from 0.0 to 1.0.
+    pub fn alpha(&self) -> f32 {
+        self.accumulator / TICK_SECONDS
+    }
+}
+
+/// Owns the `Game` resource and advances it, re-publishing its events as Bevy `Events<Event>`.
+pub(crate) struct TickPlugin;
+
+impl Plugin for TickPlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .add_resource(Game::new())
+            .init_resource::<FixedTick>()
+            .add_event::<Event>()
+            .add_system_to_stage(stage::PRE_UPDATE, accumulate_ticks.system())
+            .add_system(run_ticks.system())
+        ;
+    }
+}
+
+fn accumulate_ticks(
+    time: Res<Time>,
+    mut fixed_tick: ResMut<FixedTick>,
+) {
+    fixed_tick.accumulator += time.delta_seconds;
+    let mut pending = 0;
+    while fixed_tick.accumulator >= TICK_SECONDS {
+        fixed_tick.accumulator -= TICK_SECONDS;
+        pending += 1;
+        if pending == MAX_TICKS_PER_FRAME {
+            fixed_tick.accumulator = 0.0;
+        }
+    }
+    fixed_tick.pending = pending;
+}
+
+fn run_ticks(
+    mut game: ResMut<Game>,
+    mut fixed_tick: ResMut<FixedTick>,
+    joystick: Res<Joystick>,
+    current: Res<CurrentState>,
+    mut events: ResMut<Events<Event>>,
+) {
+    // Time spent on the title screen or paused for READY! is simply dropped.
+    if !current.runs_game() {
+        fixed_tick.pending = 0;
+        return;
+    }
+    // Once the game reports something that moves the app on, the rest of the batch is
+    // dropped: the next state may not run the game at all.
+    while fixed_tick.pending > 0 {
+        fixed_tick.pending -= 1;
+        game.tick(joystick.direction);
+        let mut changes_state = false;
+        for event in game.drain_events() {
+            changes_state |= current.changes_with(&event);
+            events.send(event);
+        }
+        if changes_state {
+            fixed_tick.pending = 0;
+        }
+    }
+}
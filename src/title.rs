@@ -0,0 +1,157 @@
+use bevy::prelude::*;
+
+use crate::consts::{SCALE, TILE_SIZE, TILES_WIDE, TILES_HIGH};
+use crate::game::Game;
+use crate::game::autopilot::Autopilot;
+use crate::game::ghost::Personality;
+use crate::man::Joystick;
+use crate::map::{DOT_SPRITE_INDEX, ENERGIZER_SPRITE_INDEX, map_tile_to_translation};
+use crate::sprite::load_sprite_sheet;
+use crate::state::{AppState, CurrentState};
+use crate::text::{TextColor, load_font, spawn_text};
+
+// The cast is introduced over a black screen, in front of whatever the maze was doing.
+const BACKDROP_Z: f32 = 4.0;
+const INTRO_Z: f32 = 5.0;
+
+const HEADER_TILE: (u32, u32) = (9, 31);
+const PORTRAIT_TILE_X: u32 = 7;
+const NICKNAME_TILE_X: u32 = 9;
+const FIRST_GHOST_TILE_Y: u32 = 29;
+const GHOST_ROWS: u32 = 3;
+const POINTS_ICON_TILE_X: u32 = 12;
+const POINTS_TEXT_TILE_X: u32 = 14;
+const DOT_TILE_Y: u32 = 10;
+const ENERGIZER_TILE_Y: u32 = 8;
+const COPYRIGHT_TILE: (u32, u32) = (6, 3);
+
+// Each ghost's portrait turns up, then its nickname a beat later, then the next ghost.
+const FIRST_GHOST_SECONDS: f32 = 1.0;
+const GHOST_SECONDS: f32 = 2.0;
+const NICKNAME_SECONDS: f32 = 1.0;
+const POINTS_SECONDS: f32 = 9.0;
+
+/// The character introduction screen, and the autopilot at the controls for the demo.
+pub(crate) struct TitlePlugin;
+
+/// Part of the introduction screen, shown once it's been up for `0` seconds.
+#[derive(Clone)]
+struct IntroPiece(f32);
+
+impl Plugin for TitlePlugin {
+    fn build(&self, app: &mut AppBuilder) {
+        app
+            .add_startup_system(setup_intro.system())
+            .add_system(show_intro.system())
+            .add_system(demo_input.system())
+        ;
+    }
+}
+
+fn setup_intro(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut textures: ResMut<Assets<Texture>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    let font = load_font(&asset_server, &mut textures, &mut texture_atlases);
+    let sheet = load_sprite_sheet(&asset_server, &mut textures, &mut texture_atlases);
+
+    commands
+        .spawn(SpriteComponents {
+            material: materials.add(Color::rgb(0.0, 0.0, 0.0).into()),
+            transform: Transform::from_translation(Vec3::new(0.0, 0.0, BACKDROP_Z)),
+            sprite: Sprite::new(Vec2::new(TILES_WIDE as f32 * TILE_SIZE * SCALE,
+                                          TILES_HIGH as f32 * TILE_SIZE * SCALE)),
+            ..Default::default()
+        })
+        .with(IntroPiece(0.0))
+    ;
+    spawn_text(
+        &mut commands, font, "CHARACTER / NICKNAME", TextColor::White,
+        HEADER_TILE, INTRO_Z, IntroPiece(0.0));
+
+    for (i, &personality) in Personality::ALL.iter().enumerate() {
+        let y = FIRST_GHOST_TILE_Y - GHOST_ROWS * i as u32;
+        let shown = FIRST_GHOST_SECONDS + GHOST_SECONDS * i as f32;
+        let (index, line, color) = cast(personality);
+        let mut translation = map_tile_to_translation(PORTRAIT_TILE_X, y, Some(0), None);
+        translation.set_z(INTRO_Z);
+        spawn_icon(&mut commands, sheet, index, translation, shown);
+        spawn_text(
+            &mut commands, font, line, color,
+            (NICKNAME_TILE_X, y), INTRO_Z, IntroPiece(shown + NICKNAME_SECONDS));
+    }
+
+    for &(index, y, points) in [
+        (DOT_SPRITE_INDEX, DOT_TILE_Y, "10 PTS"),
+        (ENERGIZER_SPRITE_INDEX, ENERGIZER_TILE_Y, "50 PTS"),
+    ].iter() {
+        let mut translation = map_tile_to_translation(POINTS_ICON_TILE_X, y, None, None);
+        translation.set_z(INTRO_Z);
+        spawn_icon(&mut commands, sheet, index, translation, POINTS_SECONDS);
+        spawn_text(
+            &mut commands, font, points, TextColor::White,
+            (POINTS_TEXT_TILE_X, y), INTRO_Z, IntroPiece(POINTS_SECONDS));
+    }
+    // '@' is the copyright sign in this font.
+    spawn_text(
+        &mut commands, font, "@ 1980 MIDWAY MFG.CO.", TextColor::Pink,
+        COPYRIGHT_TILE, INTRO_Z, IntroPiece(POINTS_SECONDS));
+}
+
+fn spawn_icon(
+    commands: &mut Commands,
+    sheet: Handle<TextureAtlas>,
+    index: u32,
+    translation: Vec3,
+    shown: f32,
+) {
+    let mut sprite_sheet_components = SpriteSheetComponents {
+        texture_atlas: sheet,
+        transform: Transform::from_scale(SCALE)
+            .with_translation(translation),
+        ..Default::default()
+    };
+    sprite_sheet_components.sprite.index = index;
+    commands
+        .spawn(sprite_sheet_components)
+        .with(IntroPiece(shown))
+    ;
+}
+
+// Portrait (facing right), nickname line and colour for each ghost.
+fn cast(personality: Personality) -> (u32, &'static str, TextColor) {
+    match personality {
+        Personality::Blinky => (56, "-SHADOW    \"BLINKY\"", TextColor::Red),
+        Personality::Pinky => (70, "-SPEEDY    \"PINKY\"", TextColor::Pink),
+        Personality::Inky => (84, "-BASHFUL   \"INKY\"", TextColor::Cyan),
+        Personality::Clyde => (98, "-POKEY     \"CLYDE\"", TextColor::Orange),
+    }
+}
+
+fn show_intro(
+    current: Res<CurrentState>,
+    mut query: Query<(&IntroPiece, &mut Draw)>,
+) {
+    let showing = current.get() == AppState::Intro;
+    let elapsed = current.elapsed();
+    for (piece, mut draw) in &mut query.iter() {
+        draw.is_visible = showing && elapsed >= piece.0;
+    }
+}
+
+// In the demo PacMan is steered by the same autopilot the headless mode uses, which keeps
+// clear of the ghosts well enough to get through most of a level. It keeps its generator
+// from one demo to the next, so no two play out the same.
+fn demo_input(
+    game: Res<Game>,
+    current: Res<CurrentState>,
+    mut autopilot: Local<Autopilot>,
+    mut joystick: ResMut<Joystick>,
+) {
+    if current.get() == AppState::Demo {
+        joystick.direction = autopilot.steer(&game);
+    }
+}